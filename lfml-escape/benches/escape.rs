@@ -0,0 +1,69 @@
+//! Compares the escapers against the byte by byte loop they replaced.
+//!
+//! Run with `cargo bench -p lfml-escape`.
+//!
+//! Text without special bytes is skipped a word at a time, so clean and non-ascii input is several
+//! times faster than the byte loop. Markup-like input, where most words need escaping, pays for the
+//! word checks and is somewhat slower. `escape_to_writer` escapes such input a chunk at a time
+//! into a buffer on the stack, which keeps it close to `escape_to_string`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The escaper as it was before scanning for special bytes a word at a time.
+fn byte_loop(input: &str, output: &mut String) {
+    for b in input.bytes() {
+        match b {
+            b'&' => output.push_str("&amp;"),
+            b'<' => output.push_str("&lt;"),
+            b'>' => output.push_str("&gt;"),
+            b'"' => output.push_str("&quot;"),
+            b'\'' => output.push_str("&#39;"),
+            // Safety: `input` is valid utf-8.
+            _ => unsafe { output.as_mut_vec().push(b) },
+        }
+    }
+}
+
+fn bench(name: &str, input: &str, f: impl Fn(&str, &mut String)) {
+    let mut output = String::with_capacity(input.len() * 2);
+    let mut iterations = 0u32;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(500) {
+        output.clear();
+        f(black_box(input), &mut output);
+        black_box(&output);
+        iterations += 1;
+    }
+    let per_iter = start.elapsed() / iterations;
+    let throughput = input.len() as f64 / per_iter.as_secs_f64() / (1024.0 * 1024.0);
+    println!("{name:<40} {per_iter:>12.2?}/iter {throughput:>10.1} MiB/s");
+}
+
+fn main() {
+    let clean = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(1000);
+    let sparse = "Lorem ipsum dolor sit amet, consectetur <b>adipiscing</b> elit. ".repeat(1000);
+    let dense = "<a href=\"/?a=1&b='2'\">&</a>".repeat(2000);
+    let unicode = "Ünïcödé téxt wïth äccénts & 🦀 crabs. ".repeat(1000);
+
+    for (input_name, input) in [
+        ("clean", &clean),
+        ("sparse", &sparse),
+        ("dense", &dense),
+        ("unicode", &unicode),
+    ] {
+        bench(&format!("{input_name}/byte_loop"), input, byte_loop);
+        bench(
+            &format!("{input_name}/escape_to_string"),
+            input,
+            lfml_escape::escape_to_string,
+        );
+        bench(
+            &format!("{input_name}/text::escape_to_string"),
+            input,
+            lfml_escape::text::escape_to_string,
+        );
+        bench(&format!("{input_name}/escape_to_writer"), input, |i, o| {
+            lfml_escape::escape_to_writer(i, o).unwrap()
+        });
+    }
+}
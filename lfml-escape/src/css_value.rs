@@ -0,0 +1,31 @@
+//! Escaping for css values, e.g. the value of a declaration in a `style` attribute, or a value
+//! interpolated into a `<style>` body.
+//!
+//! The characters which could end the declaration or its block, open or close a css string, start
+//! an escape of their own, or close the style element are replaced by css hex escapes, so the
+//! value stays the value of its property.
+use crate::scan::{ByteSet, Output};
+
+const SPECIAL: ByteSet<10> = ByteSet::new([
+    b';', b'{', b'}', b'\\', b'"', b'\'', b'<', b'>', b'\n', b'\r',
+]);
+
+#[inline(always)]
+fn escape(bytes: &[u8], i: usize, output: &mut impl Output) -> usize {
+    match bytes[i] {
+        b';' => output.extend_from_slice(b"\\3b "),
+        b'{' => output.extend_from_slice(b"\\7b "),
+        b'}' => output.extend_from_slice(b"\\7d "),
+        b'\\' => output.extend_from_slice(b"\\5c "),
+        b'"' => output.extend_from_slice(b"\\22 "),
+        b'\'' => output.extend_from_slice(b"\\27 "),
+        b'<' => output.extend_from_slice(b"\\3c "),
+        b'>' => output.extend_from_slice(b"\\3e "),
+        b'\n' => output.extend_from_slice(b"\\a "),
+        b'\r' => output.extend_from_slice(b"\\d "),
+        _ => return 0,
+    }
+    1
+}
+
+escape_fns!(SPECIAL, escape);
@@ -1,23 +1,169 @@
 extern crate alloc;
 use alloc::string::String;
 
-pub fn escape_to_string(input: &str, output: &mut String) {
-    for b in input.bytes() {
-        match b {
-            b'&' => output.push_str("&amp;"),
-            b'<' => output.push_str("&lt;"),
-            b'>' => output.push_str("&gt;"),
-            b'"' => output.push_str("&quot;"),
-            // Safety: `input` is valid utf-8.
-            _ => unsafe { output.as_mut_vec().push(b) },
+/// Defines the `escape_to_string`, `escape_to_writer` and `escape_string` of an escaper, from the
+/// bytes it escapes and the `escape` function which pushes their replacements.
+macro_rules! escape_fns {
+    ($(#[$attr:meta])* $set:ident, $escape:ident) => {
+        $(#[$attr])*
+        pub fn escape_to_string(input: &str, output: &mut alloc::string::String) {
+            $crate::push_escaped(input, output, &$set, $escape)
         }
+
+        /// Escapes `input` into any `core::fmt::Write` sink, e.g. through an [`EscapeWriter`].
+        ///
+        /// [`EscapeWriter`]: crate::EscapeWriter
+        pub fn escape_to_writer<W: core::fmt::Write + ?Sized>(
+            input: &str,
+            output: &mut W,
+        ) -> core::fmt::Result {
+            $crate::write_escaped(input, output, &$set, $escape)
+        }
+
+        pub fn escape_string(input: &str) -> alloc::string::String {
+            let mut s = alloc::string::String::new();
+            escape_to_string(input, &mut s);
+            s
+        }
+    };
+}
+
+pub mod attr;
+pub mod css_value;
+pub mod sanitize;
+mod scan;
+pub mod script;
+pub mod single_quoted_attr;
+pub mod text;
+pub mod url;
+
+use crate::scan::{Buffer, ByteSet, Output};
+
+const SPECIAL: ByteSet<5> = ByteSet::new([b'&', b'<', b'>', b'"', b'\'']);
+
+#[inline(always)]
+fn escape(bytes: &[u8], i: usize, output: &mut impl Output) -> usize {
+    // each arm pushes a constant, which is much cheaper than a copy of unknown length.
+    match bytes[i] {
+        b'&' => output.extend_from_slice(b"&amp;"),
+        b'<' => output.extend_from_slice(b"&lt;"),
+        b'>' => output.extend_from_slice(b"&gt;"),
+        b'"' => output.extend_from_slice(b"&quot;"),
+        b'\'' => output.extend_from_slice(b"&#39;"),
+        _ => return 0,
     }
+    1
 }
 
-pub fn escape_string(input: &str) -> String {
-    let mut s = String::new();
-    escape_to_string(input, &mut s);
-    s
+escape_fns! {
+    /// Escapes `input` so that it is safe in html text as well as in quoted attribute values.
+    SPECIAL,
+    escape
+}
+
+/// A `core::fmt::Write` which escapes what's written to it into `output`, so that a `Display`
+/// value is escaped as it's formatted, without formatting it into a `String` first:
+///
+/// ```
+/// use core::fmt::Write;
+/// use lfml_escape::{attr, EscapeWriter};
+///
+/// let mut output = String::from("title=\"");
+/// write!(EscapeWriter::new(&mut output, attr::escape_to_writer), "{}", "\"a\"").unwrap();
+/// assert_eq!(output, "title=\"&quot;a&quot;");
+/// ```
+///
+/// `escape` is one of the `escape_to_writer` functions, each of which escapes a char at a time,
+/// so a value can be written in pieces. [`url`] has to see the whole url to check its scheme, so it
+/// has none.
+pub struct EscapeWriter<'a, W: ?Sized> {
+    output: &'a mut W,
+    escape: fn(&str, &mut W) -> core::fmt::Result,
+}
+
+impl<'a, W: core::fmt::Write + ?Sized> EscapeWriter<'a, W> {
+    pub fn new(output: &'a mut W, escape: fn(&str, &mut W) -> core::fmt::Result) -> Self {
+        Self { output, escape }
+    }
+}
+
+impl<W: core::fmt::Write + ?Sized> core::fmt::Write for EscapeWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        (self.escape)(s, self.output)
+    }
+}
+
+/// Displays a value escaped like [`escape_string`], as it's formatted rather than through a
+/// `String`:
+///
+/// ```
+/// use lfml_escape::Escape;
+///
+/// assert_eq!(format!("title=\"{}\"", Escape("\"a\" & b")), "title=\"&quot;a&quot; &amp; b\"");
+/// ```
+pub struct Escape<T>(pub T);
+
+impl<T: core::fmt::Display> core::fmt::Display for Escape<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Write::write_fmt(
+            &mut EscapeWriter::new(f, escape_to_writer),
+            format_args!("{}", self.0),
+        )
+    }
+}
+
+/// The most bytes a byte of input is escaped into: `\u00XX` in scripts.
+const MAX_GROWTH: usize = 6;
+
+/// The input escaped into a [`Buffer`] at a time by [`write_escaped`].
+const CHUNK: usize = 128;
+
+/// Escapes `input` into `output` like [`push_escaped`]. The clean runs are written as they are,
+/// and from each byte to escape on, a chunk of input is escaped into a buffer on the stack by the
+/// same loop as for a `String`, so that densely escaped input isn't written a few bytes at a
+/// time.
+fn write_escaped<const N: usize, W: core::fmt::Write + ?Sized>(
+    input: &str,
+    output: &mut W,
+    set: &ByteSet<N>,
+    escape: impl Fn(&[u8], usize, &mut Buffer<{ CHUNK * MAX_GROWTH }>) -> usize,
+) -> core::fmt::Result {
+    let bytes = input.as_bytes();
+    let mut buffer = Buffer::new();
+    let mut i = 0;
+    while let Some(found) = set.find(&bytes[i..]) {
+        // the bytes in the sets start a char, so the run ends on a char boundary.
+        let found = i + found;
+        if found > i {
+            output.write_str(&input[i..found])?;
+        }
+
+        let mut end = (found + CHUNK).min(input.len());
+        while !input.is_char_boundary(end) {
+            end -= 1;
+        }
+        buffer.clear();
+        scan::escape_into(&bytes[found..end], &mut buffer, set, &escape);
+        // Safety: the chunk is whole chars, which `escape_into` copies or replaces with valid
+        // utf-8.
+        output.write_str(unsafe { core::str::from_utf8_unchecked(buffer.as_bytes()) })?;
+        i = end;
+    }
+    output.write_str(&input[i..])
+}
+
+/// Escapes `input` into `output`, `escape` pushing the replacements for the bytes in `set`.
+fn push_escaped<const N: usize>(
+    input: &str,
+    output: &mut String,
+    set: &ByteSet<N>,
+    escape: impl Fn(&[u8], usize, &mut alloc::vec::Vec<u8>) -> usize,
+) {
+    output.reserve(input.len());
+    // Safety: `escape_into` copies `input` byte for byte except for what `escape` replaces, and
+    // every `escape` replaces whole chars with valid utf-8.
+    let output = unsafe { output.as_mut_vec() };
+    scan::escape_into(input.as_bytes(), output, set, escape);
 }
 
 #[cfg(test)]
@@ -30,4 +176,188 @@ mod test {
         escape_to_string("<script>BadThings()</script>", &mut s);
         assert_eq!(s, "&lt;script&gt;BadThings()&lt;/script&gt;");
     }
+
+    #[test]
+    fn escape_quotes() {
+        assert_eq!(escape_string("\"'"), "&quot;&#39;");
+    }
+
+    #[test]
+    fn module_writers_match_escape_to_string() {
+        use core::fmt::Write;
+
+        let input = "a < b && \"c\" > 'd'; {e}\n\u{2028} ünïcödé \u{2029}";
+        type IntoString = fn(&str, &mut String);
+        type IntoWriter = fn(&str, &mut String) -> core::fmt::Result;
+
+        let escapers: [(IntoString, IntoWriter); 5] = [
+            (attr::escape_to_string, attr::escape_to_writer),
+            (text::escape_to_string, text::escape_to_writer),
+            (script::escape_to_string, script::escape_to_writer),
+            (css_value::escape_to_string, css_value::escape_to_writer),
+            (
+                single_quoted_attr::escape_to_string,
+                single_quoted_attr::escape_to_writer,
+            ),
+        ];
+        for (to_string, to_writer) in escapers {
+            let mut expected = String::new();
+            to_string(input, &mut expected);
+
+            let mut s = String::new();
+            // in pieces, as `Display` impls write them.
+            for piece in input.split_inclusive(' ') {
+                write!(EscapeWriter::new(&mut s, to_writer), "{piece}").unwrap();
+            }
+            assert_eq!(s, expected);
+        }
+    }
+
+    #[test]
+    fn writers_escape_across_chunks() {
+        type IntoString = fn(&str, &mut String);
+        type IntoWriter = fn(&str, &mut String) -> core::fmt::Result;
+
+        let escapers: [(IntoString, IntoWriter); 6] = [
+            (escape_to_string, escape_to_writer),
+            (attr::escape_to_string, attr::escape_to_writer),
+            (text::escape_to_string, text::escape_to_writer),
+            (script::escape_to_string, script::escape_to_writer),
+            (css_value::escape_to_string, css_value::escape_to_writer),
+            (
+                single_quoted_attr::escape_to_string,
+                single_quoted_attr::escape_to_writer,
+            ),
+        ];
+        // every byte escaped at its worst, and multi-byte chars across every chunk boundary.
+        for offset in 0..4 {
+            for unit in ["\"", "\0", "\u{2028}", "ü<", "🦀;"] {
+                let input = "a".repeat(offset) + &unit.repeat(300);
+                for (to_string, to_writer) in escapers {
+                    let mut expected = String::new();
+                    to_string(&input, &mut expected);
+
+                    let mut s = String::new();
+                    to_writer(&input, &mut s).unwrap();
+                    assert_eq!(s, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn escape_to_writer_matches_escape_to_string() {
+        let input = "a < b && \"c\" > 'd', ünïcödé";
+        let mut s = String::new();
+        escape_to_writer(input, &mut s).unwrap();
+        assert_eq!(s, escape_string(input));
+    }
+
+    /// The byte by byte loop the escapers replaced.
+    fn reference_escape(input: &str) -> String {
+        let mut output = String::new();
+        for c in input.chars() {
+            match c {
+                '&' => output.push_str("&amp;"),
+                '<' => output.push_str("&lt;"),
+                '>' => output.push_str("&gt;"),
+                '"' => output.push_str("&quot;"),
+                '\'' => output.push_str("&#39;"),
+                c => output.push(c),
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn escape_matches_reference() {
+        let pieces = [
+            "a",
+            "<",
+            "ü",
+            "&",
+            "\"",
+            "'",
+            ">",
+            "long clean run of text",
+            "🦀",
+        ];
+        let mut input = String::new();
+        for i in 0..200 {
+            input.push_str(pieces[(i * 7) % pieces.len()]);
+            assert_eq!(escape_string(&input), reference_escape(&input));
+        }
+    }
+
+    #[test]
+    fn context_escapers() {
+        let input = "<a href='x'>\"&\"</a>";
+
+        assert_eq!(
+            text::escape_string(input),
+            "&lt;a href='x'&gt;\"&amp;\"&lt;/a&gt;"
+        );
+        assert_eq!(
+            attr::escape_string(input),
+            "&lt;a href='x'&gt;&quot;&amp;&quot;&lt;/a&gt;"
+        );
+        assert_eq!(
+            single_quoted_attr::escape_string(input),
+            "&lt;a href=&#39;x&#39;&gt;\"&amp;\"&lt;/a&gt;"
+        );
+        assert_eq!(
+            script::escape_string("{\"a\":\"</script>&\u{2028}\"}"),
+            "{\\u0022a\\u0022:\\u0022\\u003c\\u002fscript\\u003e\\u0026\\u2028\\u0022}"
+        );
+        assert_eq!(script::escape_string("€\u{2029}€"), "€\\u2029€");
+        assert_eq!(
+            script::escape_string("<\u{3028}>"),
+            "\\u003c\u{3028}\\u003e"
+        );
+        assert_eq!(
+            script::escape_string("';alert(1);//\\\n\0"),
+            "\\u0027;alert(1);\\u002f\\u002f\\u005c\\u000a\\u0000"
+        );
+        assert_eq!(
+            css_value::escape_string("red; background: url(x) } a { color: blue\\"),
+            "red\\3b  background: url(x) \\7d  a \\7b  color: blue\\5c "
+        );
+        assert_eq!(
+            css_value::escape_string("\"Fira Sans\", 'serif'"),
+            "\\22 Fira Sans\\22 , \\27 serif\\27 "
+        );
+    }
+
+    #[test]
+    fn url_escaper() {
+        assert_eq!(
+            url::escape_string("/a?b=1&c=\"2\""),
+            "/a?b=1&amp;c=&quot;2&quot;"
+        );
+        assert_eq!(url::escape_string("https://x.y/"), "https://x.y/");
+        assert_eq!(url::escape_string("javascript:alert(1)"), url::UNSAFE_URL);
+        assert_eq!(url::escape_string(" JavaScript:alert(1)"), url::UNSAFE_URL);
+        assert_eq!(url::escape_string("java\tscript:alert(1)"), url::UNSAFE_URL);
+        assert_eq!(url::escape_string("vbscript:msgbox"), url::UNSAFE_URL);
+        assert_eq!(url::escape_string("/javascript:x"), "/javascript:x");
+        assert_eq!(
+            url::escape_string("data:text/html,<script>"),
+            url::UNSAFE_URL
+        );
+        assert_eq!(
+            url::escape_string("data:image/svg+xml;base64,PHN2Zz4="),
+            url::UNSAFE_URL
+        );
+        assert_eq!(
+            url::escape_string("DATA:Image/PNG;base64,iVBO"),
+            "DATA:Image/PNG;base64,iVBO"
+        );
+        assert!(!url::is_safe_escaped("javascript&colon;alert(1)"));
+        assert!(!url::is_safe_escaped("&#106;avascript:alert(1)"));
+        assert!(url::is_safe_escaped("/a?b=1&amp;c=2"));
+        assert!(!url::is_safe_escaped("javascript&colon;//%0aalert(1)"));
+        assert!(!url::is_safe_escaped("data&colon;text/html,<script>"));
+        assert!(url::is_safe_escaped("data&colon;image/png;base64,iVBO"));
+        assert!(url::is_url_attribute("HREF"));
+    }
 }
@@ -0,0 +1,425 @@
+//! Sanitizing html written by users, e.g. comments or rich text, so that it's safe to insert in a
+//! page as is.
+//!
+//! Only the tags and attributes of an allowlist are kept, everything else being dropped, and the
+//! output is always well formed: the elements left open are closed, so that they can't swallow the
+//! rest of the page. By default, the allowlist is every tag of [`lfml_html5::VALID_HTML5_TAGS`]
+//! but the [`DENIED_TAGS`], with the global and element specific attributes of
+//! [`lfml_html5::element`] but the [`DENIED_ATTRIBUTES`].
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+/// The tags left out of the default allowlist: they run code, embed other documents or media,
+/// submit data, or belong in the `<head>` of the page.
+pub const DENIED_TAGS: &[&str] = &[
+    "area", "audio", "base", "blink", "body", "button", "canvas", "datalist", "dialog", "embed",
+    "fieldset", "form", "head", "html", "iframe", "input", "label", "legend", "link", "map",
+    "marquee", "math", "meta", "noscript", "object", "optgroup", "option", "output", "param",
+    "picture", "script", "select", "slot", "source", "style", "svg", "template", "textarea",
+    "title", "track", "video",
+];
+
+/// The denied tags whose content is dropped along with them, rather than kept without the tag.
+pub const DROPPED_CONTENT_TAGS: &[&str] = &[
+    "datalist", "head", "iframe", "math", "noembed", "noframes", "noscript", "object", "script",
+    "select", "style", "svg", "template", "textarea", "title", "xmp",
+];
+
+/// Tags which are never allowed, as their content isn't html.
+const RAW_TEXT_TAGS: &[&str] = &["noembed", "noframes", "script", "style", "xmp"];
+
+/// The attributes left out of the default allowlist: they style the page, take the focus, or can
+/// clobber the ids the page relies on.
+pub const DENIED_ATTRIBUTES: &[&str] = &[
+    "accesskey",
+    "autofocus",
+    "contenteditable",
+    "id",
+    "is",
+    "nonce",
+    "ping",
+    "popover",
+    "slot",
+    "srcset",
+    "style",
+];
+
+/// The schemes allowed in urls by default, on top of relative urls.
+pub const URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+/// An allowlist of tags, attributes and url schemes, on top of or instead of the default one.
+///
+/// `"*"` stands for every tag in [`Sanitizer::allow_attribute`] and
+/// [`Sanitizer::deny_attribute`] only: [`Sanitizer::allow_tag`] and [`Sanitizer::deny_tag`] take
+/// the name of a single tag. Names are compared case-insensitively, and the last call about the
+/// same tag, or the same attribute of the same tag, wins.
+#[derive(Debug, Clone, Default)]
+pub struct Sanitizer {
+    allowed_tags: Vec<String>,
+    denied_tags: Vec<String>,
+    allowed_attributes: Vec<(String, String)>,
+    denied_attributes: Vec<(String, String)>,
+    url_schemes: Vec<String>,
+}
+
+impl Sanitizer {
+    /// The default allowlist.
+    pub const fn new() -> Self {
+        Self {
+            allowed_tags: Vec::new(),
+            denied_tags: Vec::new(),
+            allowed_attributes: Vec::new(),
+            denied_attributes: Vec::new(),
+            url_schemes: Vec::new(),
+        }
+    }
+
+    /// Allows `tag`, unless its content isn't html, i.e. `script`, `style`, `noembed`,
+    /// `noframes` and `xmp`: those are still dropped along with their content, whatever this is
+    /// called with.
+    pub fn allow_tag(mut self, tag: &str) -> Self {
+        let tag = tag.to_ascii_lowercase();
+        self.denied_tags.retain(|t| *t != tag);
+        self.allowed_tags.push(tag);
+        self
+    }
+
+    /// Drops `tag`, keeping its content unless it's one of the [`DROPPED_CONTENT_TAGS`].
+    pub fn deny_tag(mut self, tag: &str) -> Self {
+        let tag = tag.to_ascii_lowercase();
+        self.allowed_tags.retain(|t| *t != tag);
+        self.denied_tags.push(tag);
+        self
+    }
+
+    /// Allows `attr` on `tag`, or on every tag if `tag` is `"*"`, even if it's one of the
+    /// [`DENIED_ATTRIBUTES`]. Url attributes are still dropped unless their scheme is allowed.
+    pub fn allow_attribute(mut self, tag: &str, attr: &str) -> Self {
+        let entry = (tag.to_ascii_lowercase(), attr.to_ascii_lowercase());
+        self.denied_attributes.retain(|a| *a != entry);
+        self.allowed_attributes.push(entry);
+        self
+    }
+
+    /// Drops `attr` from `tag`, or from every tag if `tag` is `"*"`. Denials outweigh allowances
+    /// for the other `tag`, e.g. denying `title` on `"*"` drops it from `a` even if it was
+    /// allowed there.
+    pub fn deny_attribute(mut self, tag: &str, attr: &str) -> Self {
+        let entry = (tag.to_ascii_lowercase(), attr.to_ascii_lowercase());
+        self.allowed_attributes.retain(|a| *a != entry);
+        self.denied_attributes.push(entry);
+        self
+    }
+
+    /// Allows urls with `scheme`, on top of the [`URL_SCHEMES`].
+    pub fn allow_url_scheme(mut self, scheme: &str) -> Self {
+        self.url_schemes.push(scheme.to_ascii_lowercase());
+        self
+    }
+
+    fn allows_tag(&self, tag: &str) -> bool {
+        if RAW_TEXT_TAGS.contains(&tag) || self.denied_tags.iter().any(|t| t == tag) {
+            return false;
+        }
+        self.allowed_tags.iter().any(|t| t == tag)
+            || (lfml_html5::VALID_HTML5_TAGS.contains(&tag) && !DENIED_TAGS.contains(&tag))
+    }
+
+    fn allows_attribute(&self, tag: &str, attr: &str) -> bool {
+        let matches = |(t, a): &(String, String)| (t == tag || t == "*") && a == attr;
+        if self.denied_attributes.iter().any(matches) {
+            return false;
+        }
+        if self.allowed_attributes.iter().any(matches) {
+            return true;
+        }
+        // event handlers are left out of the defaults by not being among the listed attributes.
+        !DENIED_ATTRIBUTES.contains(&attr)
+            && (lfml_html5::GLOBAL_ATTRIBUTES.contains(&attr)
+                || attr.starts_with("aria-")
+                || lfml_html5::element(tag).is_some_and(|e| e.attributes.contains(&attr)))
+    }
+
+    fn allows_url(&self, url: &str) -> bool {
+        match crate::url::scheme(url) {
+            None => true,
+            Some(scheme) => {
+                URL_SCHEMES.contains(&scheme.as_str()) || self.url_schemes.contains(&scheme)
+            }
+        }
+    }
+
+    /// Pushes the allowed tags and attributes of `input` to `output`, escaping its text.
+    pub fn sanitize_to_string(&self, input: &str, output: &mut String) {
+        output.reserve(input.len());
+
+        // the allowed elements left open.
+        let mut open: Vec<String> = Vec::new();
+        let mut rest = input;
+
+        while !rest.is_empty() {
+            let Some(lt) = rest.find('<') else {
+                push_text(rest, output);
+                break;
+            };
+            push_text(&rest[..lt], output);
+            rest = &rest[lt..];
+
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |i| &comment[i + 3..]);
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                rest = rest.find('>').map_or("", |i| &rest[i + 1..]);
+            } else if let Some(tag) = rest.strip_prefix("</") {
+                let end = tag.find('>').map_or(tag.len(), |i| i + 1);
+                let name = tag_name(tag).to_ascii_lowercase();
+                if let Some(i) = open.iter().rposition(|t| *t == name) {
+                    for t in open.drain(i..).rev() {
+                        push_close_tag(&t, output);
+                    }
+                }
+                rest = &tag[end..];
+            } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                let name = tag_name(&rest[1..]);
+                let (attrs, end) = parse_attributes(&rest[1 + name.len()..]);
+                rest = &rest[1 + name.len() + end..];
+                let name = name.to_ascii_lowercase();
+
+                if !self.allows_tag(&name) {
+                    if DROPPED_CONTENT_TAGS.contains(&name.as_str()) {
+                        rest = skip_element(rest, &name);
+                    }
+                    continue;
+                }
+
+                output.push('<');
+                output.push_str(&name);
+                let mut seen: Vec<String> = Vec::new();
+                for (attr, value) in attrs {
+                    let attr = attr.to_ascii_lowercase();
+                    // browsers keep the first of duplicate attributes.
+                    if seen.contains(&attr) {
+                        continue;
+                    }
+                    seen.push(attr.clone());
+                    if !self.allows_attribute(&name, &attr) {
+                        continue;
+                    }
+                    let value = value.map(decode_entities);
+                    if crate::url::is_url_attribute(&attr)
+                        && !value.as_deref().is_some_and(|v| self.allows_url(v))
+                    {
+                        continue;
+                    }
+                    output.push(' ');
+                    output.push_str(&attr);
+                    if let Some(value) = value {
+                        output.push_str("=\"");
+                        crate::attr::escape_to_string(&value, output);
+                        output.push('"');
+                    }
+                }
+                output.push('>');
+
+                if !lfml_html5::is_void_element(&name) {
+                    open.push(name);
+                }
+            } else {
+                output.push_str("&lt;");
+                rest = &rest[1..];
+            }
+        }
+
+        for t in open.iter().rev() {
+            push_close_tag(t, output);
+        }
+    }
+
+    /// Returns the allowed tags and attributes of `input`, see [`Sanitizer::sanitize_to_string`].
+    pub fn sanitize_string(&self, input: &str) -> String {
+        let mut s = String::new();
+        self.sanitize_to_string(input, &mut s);
+        s
+    }
+}
+
+/// Sanitizes `input` with the default allowlist, see [`Sanitizer`].
+pub fn sanitize_to_string(input: &str, output: &mut String) {
+    Sanitizer::new().sanitize_to_string(input, output)
+}
+
+/// Returns `input` sanitized with the default allowlist, see [`Sanitizer`].
+pub fn sanitize_string(input: &str) -> String {
+    Sanitizer::new().sanitize_string(input)
+}
+
+/// Pushes text, whose character references are kept as they're written.
+fn push_text(text: &str, output: &mut String) {
+    let mut start = 0;
+    for (i, b) in text.bytes().enumerate() {
+        let escaped = match b {
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            _ => continue,
+        };
+        output.push_str(&text[start..i]);
+        output.push_str(escaped);
+        start = i + 1;
+    }
+    output.push_str(&text[start..]);
+}
+
+fn push_close_tag(name: &str, output: &mut String) {
+    output.push_str("</");
+    output.push_str(name);
+    output.push('>');
+}
+
+/// The name of the tag starting `tag`, which follows its `<` or `</`.
+fn tag_name(tag: &str) -> &str {
+    let end = tag
+        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+/// Parses the attributes following the name of a tag, up to its `>`. Returns them along with the
+/// length of the rest of the tag.
+fn parse_attributes(tag: &str) -> (Vec<(&str, Option<&str>)>, usize) {
+    let bytes = tag.as_bytes();
+    let mut attrs = Vec::new();
+    let mut i = 0;
+
+    let skip_whitespace = |i: &mut usize| {
+        while *i < bytes.len() && bytes[*i].is_ascii_whitespace() {
+            *i += 1;
+        }
+    };
+
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return (attrs, i);
+        }
+        if bytes[i] == b'>' {
+            return (attrs, i + 1);
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && !matches!(bytes[i], b'=' | b'>' | b'/') {
+            if bytes[i].is_ascii_whitespace() {
+                break;
+            }
+            i += 1;
+        }
+        let name = &tag[start..i];
+
+        let mut after_name = i;
+        skip_whitespace(&mut after_name);
+        if after_name == bytes.len() || bytes[after_name] != b'=' {
+            attrs.push((name, None));
+            continue;
+        }
+        i = after_name + 1;
+        skip_whitespace(&mut i);
+
+        let value = match bytes.get(i) {
+            Some(&q @ (b'"' | b'\'')) => {
+                let end = tag[i + 1..]
+                    .find(q as char)
+                    .map_or(tag.len(), |e| i + 1 + e);
+                let value = &tag[i + 1..end];
+                i = (end + 1).min(tag.len());
+                value
+            }
+            _ => {
+                let start = i;
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                    i += 1;
+                }
+                &tag[start..i]
+            }
+        };
+        attrs.push((name, Some(value)));
+    }
+}
+
+/// Skips the content of the element `name` and its closing tag.
+fn skip_element<'a>(rest: &'a str, name: &str) -> &'a str {
+    let mut from = 0;
+    while let Some(i) = rest[from..].find("</") {
+        let start = from + i + 2;
+        if rest
+            .get(start..start + name.len())
+            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        {
+            let end = rest[start..]
+                .find('>')
+                .map_or(rest.len(), |e| start + e + 1);
+            return &rest[end..];
+        }
+        from = start;
+    }
+    ""
+}
+
+/// Decodes the numeric and most common named character references of an attribute value, which
+/// is then escaped again: references left as they are are displayed as written, rather than
+/// decoded by browsers.
+pub(crate) fn decode_entities(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let (decoded, len) = decode_entity(rest).unwrap_or(('&', 1));
+        out.push(decoded);
+        rest = &rest[len..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// The char referenced at the start of `s`, and the length of the reference.
+fn decode_entity(s: &str) -> Option<(char, usize)> {
+    if let Some(num) = s.strip_prefix("&#") {
+        let (digits, radix, prefix) = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 3),
+            None => (num, 10, 2),
+        };
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if len == 0 {
+            return None;
+        }
+        let c = u32::from_str_radix(&digits[..len], radix)
+            .ok()
+            .and_then(char::from_u32)
+            .filter(|&c| c != '\0')
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        let semicolon = digits[len..].starts_with(';') as usize;
+        return Some((c, prefix + len + semicolon));
+    }
+
+    let end = s.find(';')?;
+    let c = match &s[1..end] {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "colon" => ':',
+        "Tab" => '\t',
+        "NewLine" => '\n',
+        _ => return None,
+    };
+    Some((c, end + 1))
+}
@@ -0,0 +1,199 @@
+//! Finding the bytes which need escaping, 8 bytes at a time.
+//!
+//! Each needle is broadcast to every byte of a `u64`, so that a word of input can be checked for
+//! any of the needles with a handful of arithmetic operations (see "determine if a word has a
+//! zero byte" in [bit twiddling hacks]). Clean words are skipped, and copied out as part of a run
+//! in one go, only the words containing a needle are handled byte by byte.
+//!
+//! [bit twiddling hacks]: https://graphics.stanford.edu/~seander/bithacks.html#ZeroInWord
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+const LO: u64 = u64::from_ne_bytes([0x01; 8]);
+const HI: u64 = u64::from_ne_bytes([0x80; 8]);
+
+/// A set of bytes to search for.
+pub(crate) struct ByteSet<const N: usize> {
+    needles: [u64; N],
+    table: [bool; 256],
+}
+
+impl<const N: usize> ByteSet<N> {
+    pub(crate) const fn new(bytes: [u8; N]) -> Self {
+        let mut needles = [0; N];
+        let mut table = [false; 256];
+        let mut i = 0;
+        while i < N {
+            needles[i] = LO * bytes[i] as u64;
+            table[bytes[i] as usize] = true;
+            i += 1;
+        }
+        ByteSet { needles, table }
+    }
+
+    #[inline]
+    fn contains(&self, b: u8) -> bool {
+        self.table[b as usize]
+    }
+
+    #[inline]
+    fn word_contains(&self, word: &[u8]) -> bool {
+        let word = u64::from_ne_bytes(word.try_into().expect("word of 8 bytes"));
+        let mut found = 0;
+        for needle in self.needles {
+            let x = word ^ needle;
+            found |= x.wrapping_sub(LO) & !x & HI;
+        }
+        found != 0
+    }
+
+    /// Index of the first byte of `bytes` in the set.
+    #[inline]
+    pub(crate) fn find(&self, bytes: &[u8]) -> Option<usize> {
+        let mut chunks = bytes.chunks_exact(8);
+        let mut offset = 0;
+        for chunk in &mut chunks {
+            if self.word_contains(chunk) {
+                break;
+            }
+            offset += 8;
+        }
+        bytes[offset..]
+            .iter()
+            .position(|&b| self.contains(b))
+            .map(|i| offset + i)
+    }
+}
+
+/// Where [`escape_into`] copies to: the `Vec` of a `String`, or a [`Buffer`] on the stack.
+pub(crate) trait Output {
+    fn push(&mut self, b: u8);
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+}
+
+impl Output for Vec<u8> {
+    #[inline]
+    fn push(&mut self, b: u8) {
+        Vec::push(self, b)
+    }
+
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes)
+    }
+}
+
+/// A fixed size buffer, for escaping a chunk of input at a time without allocating. It's left
+/// uninitialized, as most of it goes unused for short values. Pushing past its end panics: the
+/// chunks are sized so that their escaped form always fits.
+pub(crate) struct Buffer<const N: usize> {
+    bytes: [MaybeUninit<u8>; N],
+    len: usize,
+}
+
+impl<const N: usize> Buffer<N> {
+    pub(crate) const fn new() -> Self {
+        Buffer {
+            bytes: [MaybeUninit::uninit(); N],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        // Safety: the first `len` bytes have been written.
+        unsafe { core::slice::from_raw_parts(self.bytes.as_ptr().cast(), self.len) }
+    }
+}
+
+impl<const N: usize> Output for Buffer<N> {
+    #[inline]
+    fn push(&mut self, b: u8) {
+        self.bytes[self.len].write(b);
+        self.len += 1;
+    }
+
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        assert!(end <= N, "the escaped chunk fits the buffer");
+        // Safety: `bytes` fits in the rest of the buffer, which it can't overlap.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.bytes.as_mut_ptr().add(self.len).cast(),
+                bytes.len(),
+            );
+        }
+        self.len = end;
+    }
+}
+
+/// Copies `input` into `output`, calling `escape` for the bytes in `set`.
+///
+/// `escape` is given the input and the index of the byte to escape, pushes the replacement and
+/// returns the number of bytes it replaced, or it returns 0 to leave the byte as is.
+///
+/// Within a word containing a needle, the bytes are copied one at a time: for densely escaped
+/// input, that is cheaper than copying out each of the short runs between escapes.
+#[inline]
+pub(crate) fn escape_into<const N: usize, O: Output>(
+    input: &[u8],
+    output: &mut O,
+    set: &ByteSet<N>,
+    escape: impl Fn(&[u8], usize, &mut O) -> usize,
+) {
+    let mut start = 0;
+    let mut i = 0;
+    while i < input.len() {
+        if let Some(word) = input.get(i..i + 8) {
+            if !set.word_contains(word) {
+                i += 8;
+                continue;
+            }
+        }
+
+        output.extend_from_slice(&input[start..i]);
+
+        let end = (i + 8).min(input.len());
+        while i < end {
+            match escape(input, i, output) {
+                0 => {
+                    output.push(input[i]);
+                    i += 1;
+                }
+                len => i += len,
+            }
+        }
+        start = i;
+    }
+    output.extend_from_slice(&input[start..]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find_at_every_offset() {
+        let set = ByteSet::new([b'<', b'&']);
+        for len in 0..20 {
+            for at in 0..len {
+                let mut bytes = vec![b'a'; len];
+                bytes[at] = b'&';
+                assert_eq!(set.find(&bytes), Some(at));
+            }
+            assert_eq!(set.find(&vec![b'a'; len]), None);
+        }
+    }
+
+    #[test]
+    fn no_false_positives_for_neighbouring_bytes() {
+        let set = ByteSet::new([b'<']);
+        let bytes = (0..=255u8).filter(|&b| b != b'<').collect::<Vec<_>>();
+        assert_eq!(set.find(&bytes), None);
+    }
+}
@@ -0,0 +1,40 @@
+//! Escaping for values interpolated into a `<script>` body, as the content of a JSON string.
+//!
+//! Quotes, backslashes, slashes, control characters, and the characters which could close the
+//! script element or start an html comment are replaced by their unicode escapes, so the value
+//! can't end its string literal, whether it's quoted with `"` or `'`, nor the script. Line and
+//! paragraph separators are escaped too, as they terminate javascript string literals. `html!`
+//! puts the double quotes around the value, so that it's always a string.
+use crate::scan::{ByteSet, Output};
+
+/// `0xE2` is the utf-8 lead byte of U+2028 and U+2029.
+const SPECIAL: ByteSet<40> = ByteSet::new([
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+    b'"', b'\'', b'\\', b'/', b'<', b'>', b'&', 0xE2,
+]);
+
+#[inline(always)]
+fn escape(bytes: &[u8], i: usize, output: &mut impl Output) -> usize {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    match bytes[i] {
+        b @ (0x00..=0x1F | b'"' | b'\'' | b'\\' | b'/' | b'<' | b'>' | b'&') => {
+            output.extend_from_slice(b"\\u00");
+            output.push(HEX[usize::from(b >> 4)]);
+            output.push(HEX[usize::from(b & 0xF)]);
+        }
+        0xE2 => {
+            match bytes.get(i + 1..i + 3) {
+                Some([0x80, 0xA8]) => output.extend_from_slice(b"\\u2028"),
+                Some([0x80, 0xA9]) => output.extend_from_slice(b"\\u2029"),
+                _ => return 0,
+            }
+            return 3;
+        }
+        _ => return 0,
+    }
+    1
+}
+
+escape_fns!(SPECIAL, escape);
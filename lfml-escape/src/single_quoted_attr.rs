@@ -0,0 +1,18 @@
+//! Escaping for single quoted attribute values, e.g. `name='{value}'`.
+use crate::scan::{ByteSet, Output};
+
+const SPECIAL: ByteSet<4> = ByteSet::new([b'&', b'<', b'>', b'\'']);
+
+#[inline(always)]
+fn escape(bytes: &[u8], i: usize, output: &mut impl Output) -> usize {
+    match bytes[i] {
+        b'&' => output.extend_from_slice(b"&amp;"),
+        b'<' => output.extend_from_slice(b"&lt;"),
+        b'>' => output.extend_from_slice(b"&gt;"),
+        b'\'' => output.extend_from_slice(b"&#39;"),
+        _ => return 0,
+    }
+    1
+}
+
+escape_fns!(SPECIAL, escape);
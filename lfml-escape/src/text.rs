@@ -0,0 +1,17 @@
+//! Escaping for html text content, i.e. outside of any tag.
+use crate::scan::{ByteSet, Output};
+
+const SPECIAL: ByteSet<3> = ByteSet::new([b'&', b'<', b'>']);
+
+#[inline(always)]
+fn escape(bytes: &[u8], i: usize, output: &mut impl Output) -> usize {
+    match bytes[i] {
+        b'&' => output.extend_from_slice(b"&amp;"),
+        b'<' => output.extend_from_slice(b"&lt;"),
+        b'>' => output.extend_from_slice(b"&gt;"),
+        _ => return 0,
+    }
+    1
+}
+
+escape_fns!(SPECIAL, escape);
@@ -0,0 +1,115 @@
+//! Escaping for double quoted attribute values holding a url, e.g. `href`, `src` or `action`.
+//!
+//! On top of the attribute escaping, urls with a scheme that executes code (`javascript:`,
+//! `vbscript:`) are replaced with [`UNSAFE_URL`], as are `data:` urls other than raster images,
+//! which can hold a document with scripts, e.g. in an `<iframe src>` or `<object data>`.
+use alloc::string::String;
+
+/// What an unsafe url is rendered as: it is inert, and easy to search for.
+pub const UNSAFE_URL: &str = "about:invalid#lfml-unsafe-url";
+
+const UNSAFE_SCHEMES: &[&str] = &["javascript", "vbscript"];
+
+/// The media types of the `data:` urls which are safe: images which can't hold a script, unlike
+/// e.g. `image/svg+xml`.
+const SAFE_DATA_TYPES: &[&str] = &[
+    "image/avif",
+    "image/bmp",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+];
+
+/// Attributes whose value is a url.
+pub const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "data",
+    "formaction",
+    "href",
+    "manifest",
+    "ping",
+    "poster",
+    "src",
+];
+
+/// Whether the attribute `name`, in any case, is one of the [`URL_ATTRIBUTES`].
+pub fn is_url_attribute(name: &str) -> bool {
+    URL_ATTRIBUTES.iter().any(|a| a.eq_ignore_ascii_case(name))
+}
+
+/// Whether `url` is either relative, or has a scheme which doesn't execute code. `data:` urls are
+/// only safe for raster images: avif, bmp, gif, jpeg, png and webp.
+pub fn is_safe(url: &str) -> bool {
+    match scheme(url).as_deref() {
+        Some("data") => is_safe_data(url),
+        Some(s) => !UNSAFE_SCHEMES.contains(&s),
+        None => true,
+    }
+}
+
+/// Like [`is_safe`], for a url which is escaped for an attribute value already: the character
+/// references browsers decode, e.g. `javascript&colon;`, are decoded first.
+pub fn is_safe_escaped(url: &str) -> bool {
+    // the scheme ends before the first `/` or `?`, which no character reference contains, so only
+    // that part needs decoding, unless the media type of a `data:` url has to be checked too.
+    let end = url.find(['/', '?']).unwrap_or(url.len());
+    match scheme(&crate::sanitize::decode_entities(&url[..end])).as_deref() {
+        Some("data") => is_safe(&crate::sanitize::decode_entities(url)),
+        Some(s) => !UNSAFE_SCHEMES.contains(&s),
+        None => true,
+    }
+}
+
+/// Whether the `data:` url `url` holds one of the [`SAFE_DATA_TYPES`].
+fn is_safe_data(url: &str) -> bool {
+    let Some((_, data)) = url.split_once(':') else {
+        return false;
+    };
+    let end = data.find([';', ',']).unwrap_or(data.len());
+    let media_type: String = data[..end]
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    SAFE_DATA_TYPES.contains(&media_type.as_str())
+}
+
+/// The scheme of `url` in lowercase, or `None` if it's relative.
+pub(crate) fn scheme(url: &str) -> Option<String> {
+    // browsers ignore leading whitespace and control characters, as well as tabs and newlines
+    // anywhere in the url.
+    let url = url.trim_start_matches(|c: char| c <= ' ');
+
+    let colon = url.find(':')?;
+    let scheme = &url[..colon];
+
+    if scheme.contains(['/', '?', '#']) {
+        // the colon is in the path, query or fragment of a relative url.
+        return None;
+    }
+
+    Some(
+        scheme
+            .chars()
+            .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+            .map(|c| c.to_ascii_lowercase())
+            .collect(),
+    )
+}
+
+pub fn escape_to_string(input: &str, output: &mut String) {
+    if is_safe(input) {
+        crate::attr::escape_to_string(input, output);
+    } else {
+        output.push_str(UNSAFE_URL);
+    }
+}
+
+pub fn escape_string(input: &str) -> String {
+    let mut s = String::new();
+    escape_to_string(input, &mut s);
+    s
+}
@@ -0,0 +1,1156 @@
+/// The HTML5 content model of an element, i.e. what it is allowed to contain.
+///
+/// This is a simplification of the [WHATWG content models], sufficient to catch the common
+/// nesting mistakes at compile time.
+///
+/// [WHATWG content models]: https://html.spec.whatwg.org/multipage/dom.html#content-models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentModel {
+    /// Any flow content, e.g. `div`.
+    Flow,
+    /// Phrasing content only, e.g. `p` or `span`.
+    Phrasing,
+    /// Only the listed elements, e.g. `ul` only accepts `li`.
+    Only(&'static [&'static str]),
+    /// Whatever the parent element accepts, e.g. `a`.
+    Transparent,
+    /// Text only, no child elements, e.g. `title` or `script`.
+    Text,
+    /// No content at all.
+    Empty,
+    /// Content from another namespace (`svg`, `math`), which is not validated.
+    Foreign,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Element {
+    pub name: &'static str,
+    /// Whether the element is phrasing content, and so can appear inside e.g. a `p`.
+    pub phrasing: bool,
+    pub content: ContentModel,
+    /// Element specific attributes, on top of [`GLOBAL_ATTRIBUTES`].
+    pub attributes: &'static [&'static str],
+    /// If the element may only appear as the direct child of certain elements, e.g. `li`.
+    pub parents: Option<&'static [&'static str]>,
+}
+
+impl Element {
+    /// Whether `attr` is allowed on this element: either a global, `data-*`, `aria-*` or event
+    /// handler attribute, or one specific to the element.
+    pub fn allows_attribute(&self, attr: &str) -> bool {
+        self.content == ContentModel::Foreign
+            || is_global_attribute(attr)
+            || self.attributes.contains(&attr)
+    }
+
+    /// Whether `child` may appear directly inside this element, ignoring transparency.
+    pub fn allows_child(&self, child: &Element) -> bool {
+        match self.content {
+            ContentModel::Flow | ContentModel::Transparent | ContentModel::Foreign => true,
+            ContentModel::Phrasing => child.phrasing,
+            ContentModel::Only(children) => children.contains(&child.name),
+            ContentModel::Text | ContentModel::Empty => false,
+        }
+    }
+}
+
+/// Attributes that are allowed on every element.
+pub const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "accesskey",
+    "autocapitalize",
+    "autocorrect",
+    "autofocus",
+    "class",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "enterkeyhint",
+    "hidden",
+    "id",
+    "inert",
+    "inputmode",
+    "is",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "lang",
+    "nonce",
+    "popover",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+    "writingsuggestions",
+];
+
+pub fn is_global_attribute(attr: &str) -> bool {
+    GLOBAL_ATTRIBUTES.contains(&attr)
+        || attr.starts_with("data-")
+        || attr.starts_with("aria-")
+        || (attr.starts_with("on") && attr.len() > 2)
+}
+
+/// Looks up an element in [`ELEMENTS`] by tag name.
+pub fn element(name: &str) -> Option<&'static Element> {
+    ELEMENTS
+        .binary_search_by(|e| e.name.cmp(name))
+        .ok()
+        .map(|i| &ELEMENTS[i])
+}
+
+/// Every element in [`crate::VALID_HTML5_TAGS`], sorted by name.
+pub const ELEMENTS: &[Element] = &[
+    Element {
+        name: "a",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &[
+            "href",
+            "target",
+            "download",
+            "ping",
+            "rel",
+            "hreflang",
+            "type",
+            "referrerpolicy",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "abbr",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "address",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "area",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &[
+            "alt",
+            "coords",
+            "shape",
+            "href",
+            "target",
+            "download",
+            "ping",
+            "rel",
+            "referrerpolicy",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "article",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "aside",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "audio",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &[
+            "src",
+            "crossorigin",
+            "preload",
+            "autoplay",
+            "loop",
+            "muted",
+            "controls",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "b",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "base",
+        phrasing: false,
+        content: ContentModel::Empty,
+        attributes: &["href", "target"],
+        parents: Some(&["head"]),
+    },
+    Element {
+        name: "bdi",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "bdo",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "blink",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "blockquote",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &["cite"],
+        parents: None,
+    },
+    Element {
+        name: "body",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: Some(&["html"]),
+    },
+    Element {
+        name: "br",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "button",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "name",
+            "popovertarget",
+            "popovertargetaction",
+            "type",
+            "value",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "canvas",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &["width", "height"],
+        parents: None,
+    },
+    Element {
+        name: "caption",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: Some(&["table"]),
+    },
+    Element {
+        name: "cite",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "code",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "col",
+        phrasing: false,
+        content: ContentModel::Empty,
+        attributes: &["span"],
+        parents: Some(&["colgroup"]),
+    },
+    Element {
+        name: "colgroup",
+        phrasing: false,
+        content: ContentModel::Only(&["col", "template"]),
+        attributes: &["span"],
+        parents: Some(&["table"]),
+    },
+    Element {
+        name: "data",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &["value"],
+        parents: None,
+    },
+    Element {
+        name: "datalist",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "dd",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: Some(&["dl", "div"]),
+    },
+    Element {
+        name: "del",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &["cite", "datetime"],
+        parents: None,
+    },
+    Element {
+        name: "details",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &["open", "name"],
+        parents: None,
+    },
+    Element {
+        name: "dfn",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "dialog",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &["open"],
+        parents: None,
+    },
+    Element {
+        name: "div",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "dl",
+        phrasing: false,
+        content: ContentModel::Only(&["dt", "dd", "div", "script", "template"]),
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "dt",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: Some(&["dl", "div"]),
+    },
+    Element {
+        name: "em",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "embed",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &["src", "type", "width", "height"],
+        parents: None,
+    },
+    Element {
+        name: "fieldset",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &["disabled", "form", "name"],
+        parents: None,
+    },
+    Element {
+        name: "figcaption",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: Some(&["figure"]),
+    },
+    Element {
+        name: "figure",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "footer",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "form",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[
+            "accept-charset",
+            "action",
+            "autocomplete",
+            "enctype",
+            "method",
+            "name",
+            "novalidate",
+            "target",
+            "rel",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "h1",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "h2",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "h3",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "h4",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "h5",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "h6",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "head",
+        phrasing: false,
+        content: ContentModel::Only(&[
+            "base", "link", "meta", "noscript", "script", "style", "template", "title",
+        ]),
+        attributes: &[],
+        parents: Some(&["html"]),
+    },
+    Element {
+        name: "header",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "hgroup",
+        phrasing: false,
+        content: ContentModel::Only(&[
+            "p", "h1", "h2", "h3", "h4", "h5", "h6", "script", "template",
+        ]),
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "hr",
+        phrasing: false,
+        content: ContentModel::Empty,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "html",
+        phrasing: false,
+        content: ContentModel::Only(&["head", "body"]),
+        attributes: &[],
+        parents: Some(&[]),
+    },
+    Element {
+        name: "i",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "iframe",
+        phrasing: true,
+        content: ContentModel::Text,
+        attributes: &[
+            "src",
+            "srcdoc",
+            "name",
+            "sandbox",
+            "allow",
+            "allowfullscreen",
+            "width",
+            "height",
+            "referrerpolicy",
+            "loading",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "img",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &[
+            "alt",
+            "src",
+            "srcset",
+            "sizes",
+            "crossorigin",
+            "usemap",
+            "ismap",
+            "width",
+            "height",
+            "referrerpolicy",
+            "decoding",
+            "loading",
+            "fetchpriority",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "input",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &[
+            "accept",
+            "alt",
+            "autocomplete",
+            "checked",
+            "dirname",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "height",
+            "list",
+            "max",
+            "maxlength",
+            "min",
+            "minlength",
+            "multiple",
+            "name",
+            "pattern",
+            "placeholder",
+            "popovertarget",
+            "popovertargetaction",
+            "readonly",
+            "required",
+            "size",
+            "src",
+            "step",
+            "type",
+            "value",
+            "width",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "ins",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &["cite", "datetime"],
+        parents: None,
+    },
+    Element {
+        name: "kbd",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "label",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &["for"],
+        parents: None,
+    },
+    Element {
+        name: "legend",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: Some(&["fieldset"]),
+    },
+    Element {
+        name: "li",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &["value"],
+        parents: Some(&["ul", "ol", "menu"]),
+    },
+    Element {
+        name: "link",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &[
+            "href",
+            "crossorigin",
+            "rel",
+            "as",
+            "media",
+            "hreflang",
+            "type",
+            "referrerpolicy",
+            "sizes",
+            "imagesrcset",
+            "imagesizes",
+            "integrity",
+            "blocking",
+            "color",
+            "disabled",
+            "fetchpriority",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "main",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "map",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &["name"],
+        parents: None,
+    },
+    Element {
+        name: "mark",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "marquee",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[
+            "behavior",
+            "bgcolor",
+            "direction",
+            "height",
+            "hspace",
+            "loop",
+            "scrollamount",
+            "scrolldelay",
+            "truespeed",
+            "vspace",
+            "width",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "math",
+        phrasing: true,
+        content: ContentModel::Foreign,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "menu",
+        phrasing: false,
+        content: ContentModel::Only(&["li", "script", "template"]),
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "meta",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &["name", "http-equiv", "content", "charset", "media"],
+        parents: None,
+    },
+    Element {
+        name: "meter",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &["value", "min", "max", "low", "high", "optimum"],
+        parents: None,
+    },
+    Element {
+        name: "nav",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "noscript",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "object",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &["data", "type", "name", "form", "width", "height"],
+        parents: None,
+    },
+    Element {
+        name: "ol",
+        phrasing: false,
+        content: ContentModel::Only(&["li", "script", "template"]),
+        attributes: &["reversed", "start", "type"],
+        parents: None,
+    },
+    Element {
+        name: "optgroup",
+        phrasing: false,
+        content: ContentModel::Only(&["option", "script", "template"]),
+        attributes: &["disabled", "label"],
+        parents: Some(&["select"]),
+    },
+    Element {
+        name: "option",
+        phrasing: false,
+        content: ContentModel::Text,
+        attributes: &["disabled", "label", "selected", "value"],
+        parents: Some(&["select", "datalist", "optgroup"]),
+    },
+    Element {
+        name: "output",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &["for", "form", "name"],
+        parents: None,
+    },
+    Element {
+        name: "p",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "param",
+        phrasing: false,
+        content: ContentModel::Empty,
+        attributes: &["name", "value"],
+        parents: Some(&["object"]),
+    },
+    Element {
+        name: "picture",
+        phrasing: true,
+        content: ContentModel::Only(&["source", "img", "script", "template"]),
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "pre",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "progress",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &["value", "max"],
+        parents: None,
+    },
+    Element {
+        name: "q",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &["cite"],
+        parents: None,
+    },
+    Element {
+        name: "rp",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: Some(&["ruby"]),
+    },
+    Element {
+        name: "rt",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: Some(&["ruby"]),
+    },
+    Element {
+        name: "ruby",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "s",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "samp",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "script",
+        phrasing: true,
+        content: ContentModel::Text,
+        attributes: &[
+            "src",
+            "type",
+            "nomodule",
+            "async",
+            "defer",
+            "crossorigin",
+            "integrity",
+            "referrerpolicy",
+            "blocking",
+            "fetchpriority",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "search",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "section",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "select",
+        phrasing: true,
+        content: ContentModel::Only(&["option", "optgroup", "hr", "script", "template"]),
+        attributes: &[
+            "autocomplete",
+            "disabled",
+            "form",
+            "multiple",
+            "name",
+            "required",
+            "size",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "slot",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &["name"],
+        parents: None,
+    },
+    Element {
+        name: "small",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "source",
+        phrasing: false,
+        content: ContentModel::Empty,
+        attributes: &["type", "media", "src", "srcset", "sizes", "width", "height"],
+        parents: Some(&["audio", "video", "picture"]),
+    },
+    Element {
+        name: "span",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "strong",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "style",
+        phrasing: false,
+        content: ContentModel::Text,
+        attributes: &["media", "blocking"],
+        parents: None,
+    },
+    Element {
+        name: "sub",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "summary",
+        phrasing: false,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: Some(&["details"]),
+    },
+    Element {
+        name: "sup",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "svg",
+        phrasing: true,
+        content: ContentModel::Foreign,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "table",
+        phrasing: false,
+        content: ContentModel::Only(&[
+            "caption", "colgroup", "thead", "tbody", "tfoot", "tr", "script", "template",
+        ]),
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "tbody",
+        phrasing: false,
+        content: ContentModel::Only(&["tr", "script", "template"]),
+        attributes: &[],
+        parents: Some(&["table"]),
+    },
+    Element {
+        name: "td",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &["colspan", "rowspan", "headers"],
+        parents: Some(&["tr"]),
+    },
+    Element {
+        name: "template",
+        phrasing: true,
+        content: ContentModel::Flow,
+        attributes: &[
+            "shadowrootmode",
+            "shadowrootdelegatesfocus",
+            "shadowrootclonable",
+            "shadowrootserializable",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "textarea",
+        phrasing: true,
+        content: ContentModel::Text,
+        attributes: &[
+            "autocomplete",
+            "cols",
+            "dirname",
+            "disabled",
+            "form",
+            "maxlength",
+            "minlength",
+            "name",
+            "placeholder",
+            "readonly",
+            "required",
+            "rows",
+            "wrap",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "tfoot",
+        phrasing: false,
+        content: ContentModel::Only(&["tr", "script", "template"]),
+        attributes: &[],
+        parents: Some(&["table"]),
+    },
+    Element {
+        name: "th",
+        phrasing: false,
+        content: ContentModel::Flow,
+        attributes: &["colspan", "rowspan", "headers", "scope", "abbr"],
+        parents: Some(&["tr"]),
+    },
+    Element {
+        name: "thead",
+        phrasing: false,
+        content: ContentModel::Only(&["tr", "script", "template"]),
+        attributes: &[],
+        parents: Some(&["table"]),
+    },
+    Element {
+        name: "time",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &["datetime"],
+        parents: None,
+    },
+    Element {
+        name: "title",
+        phrasing: false,
+        content: ContentModel::Text,
+        attributes: &[],
+        parents: Some(&["head"]),
+    },
+    Element {
+        name: "tr",
+        phrasing: false,
+        content: ContentModel::Only(&["td", "th", "script", "template"]),
+        attributes: &[],
+        parents: Some(&["table", "thead", "tbody", "tfoot"]),
+    },
+    Element {
+        name: "track",
+        phrasing: false,
+        content: ContentModel::Empty,
+        attributes: &["default", "kind", "label", "src", "srclang"],
+        parents: Some(&["audio", "video"]),
+    },
+    Element {
+        name: "u",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "ul",
+        phrasing: false,
+        content: ContentModel::Only(&["li", "script", "template"]),
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "var",
+        phrasing: true,
+        content: ContentModel::Phrasing,
+        attributes: &[],
+        parents: None,
+    },
+    Element {
+        name: "video",
+        phrasing: true,
+        content: ContentModel::Transparent,
+        attributes: &[
+            "src",
+            "crossorigin",
+            "poster",
+            "preload",
+            "autoplay",
+            "playsinline",
+            "loop",
+            "muted",
+            "controls",
+            "width",
+            "height",
+        ],
+        parents: None,
+    },
+    Element {
+        name: "wbr",
+        phrasing: true,
+        content: ContentModel::Empty,
+        attributes: &[],
+        parents: None,
+    },
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{VALID_HTML5_TAGS, VOID_ELEMENTS};
+
+    #[test]
+    fn elements_match_valid_tags() {
+        let names = ELEMENTS.iter().map(|e| e.name).collect::<Vec<_>>();
+        assert_eq!(names, VALID_HTML5_TAGS);
+    }
+
+    #[test]
+    fn empty_elements_are_void() {
+        let names = ELEMENTS
+            .iter()
+            .filter(|e| e.content == ContentModel::Empty)
+            .map(|e| e.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, VOID_ELEMENTS);
+    }
+
+    #[test]
+    fn content_model() {
+        let p = element("p").unwrap();
+        let ul = element("ul").unwrap();
+
+        assert!(p.allows_child(element("span").unwrap()));
+        assert!(!p.allows_child(element("div").unwrap()));
+        assert!(ul.allows_child(element("li").unwrap()));
+        assert!(!ul.allows_child(element("p").unwrap()));
+        assert!(element("a").unwrap().allows_attribute("href"));
+        assert!(element("div").unwrap().allows_attribute("data-foo"));
+        assert!(!element("div").unwrap().allows_attribute("href"));
+    }
+}
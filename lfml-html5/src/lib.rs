@@ -1,5 +1,21 @@
+mod elements;
+
+pub use crate::elements::{
+    element, is_global_attribute, ContentModel, Element, ELEMENTS, GLOBAL_ATTRIBUTES,
+};
+
 pub const DOCTYPE: &str = "<!doctype html>";
 
+/// Elements which never have any content, and so never have a closing tag.
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+pub fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag)
+}
+
 pub const VALID_HTML5_TAGS: &[&str] = &[
     "a",
     "abbr",
@@ -29,6 +45,7 @@ pub const VALID_HTML5_TAGS: &[&str] = &[
     "del",
     "details",
     "dfn",
+    "dialog",
     "div",
     "dl",
     "dt",
@@ -64,6 +81,8 @@ pub const VALID_HTML5_TAGS: &[&str] = &[
     "map",
     "mark",
     "marquee",
+    "math",
+    "menu",
     "meta",
     "meter",
     "nav",
@@ -75,15 +94,20 @@ pub const VALID_HTML5_TAGS: &[&str] = &[
     "output",
     "p",
     "param",
+    "picture",
     "pre",
     "progress",
     "q",
+    "rp",
+    "rt",
     "ruby",
     "s",
     "samp",
     "script",
+    "search",
     "section",
     "select",
+    "slot",
     "small",
     "source",
     "span",
@@ -92,6 +116,7 @@ pub const VALID_HTML5_TAGS: &[&str] = &[
     "sub",
     "summary",
     "sup",
+    "svg",
     "table",
     "tbody",
     "td",
@@ -104,6 +129,7 @@ pub const VALID_HTML5_TAGS: &[&str] = &[
     "title",
     "tr",
     "track",
+    "u",
     "ul",
     "var",
     "video",
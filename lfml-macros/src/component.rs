@@ -0,0 +1,277 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_quote, spanned::Spanned, visit::Visit, visit_mut::VisitMut, FnArg, GenericParam, Item,
+    ItemFn, ItemStruct, Lifetime, LifetimeParam, Pat, PatIdent, PathArguments, Type,
+};
+
+/// The lifetime given to the references in a function component's arguments, which are elided in
+/// the signature but need naming in the props struct.
+const PROPS_LIFETIME: &str = "'__lfml_props";
+
+/// `#[component]` on a function or on a struct with named fields.
+///
+/// Both are invoked from `html!` as `Name #(#prop=#value)* { #children }`, which expands to
+/// `Name(NameProps { #(#prop: #value,)* children: #children })`, so this generates:
+/// - for a function, a `NameProps` struct with a field per argument, and rewrites the function to
+///   take it instead of its arguments. `lfml::Slot` arguments go in a `NameSlots` struct taken as
+///   a second argument instead, which only layouts, i.e. components with slots, take.
+/// - for a struct, a `NameProps` alias of the struct and a `Name` function returning its props,
+///   the struct itself being expected to implement `lfml::Render`.
+pub fn generate_component(args: TokenStream, item: Item) -> syn::Result<TokenStream> {
+    if !args.is_empty() {
+        return Err(syn::Error::new_spanned(
+            args,
+            "`#[component]` doesn't take any arguments",
+        ));
+    }
+
+    match item {
+        Item::Fn(f) => generate_fn_component(f),
+        Item::Struct(s) => generate_struct_component(s),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`#[component]` expects a function or a struct",
+        )),
+    }
+}
+
+fn generate_fn_component(mut f: ItemFn) -> syn::Result<TokenStream> {
+    let vis = &f.vis;
+    let name = &f.sig.ident;
+    let props = format_ident!("{}Props", name);
+
+    let mut lifetimes = NameElidedLifetimes {
+        lifetime: Lifetime::new(PROPS_LIFETIME, Span::call_site()),
+        found: false,
+    };
+    let mut fields = vec![];
+    let mut patterns = vec![];
+    // `lfml::Slot` props are passed in their own struct, so that they can be left out.
+    let mut slot_fields = vec![];
+    let mut slot_patterns = vec![];
+    let mut slot_lifetimes = vec![];
+    let mut slot_checks = vec![];
+    let mut props_lifetimes = vec![];
+
+    for arg in &f.sig.inputs {
+        let arg = match arg {
+            FnArg::Typed(arg) => arg,
+            FnArg::Receiver(r) => {
+                return Err(syn::Error::new_spanned(r, "components can't take `self`"))
+            }
+        };
+
+        let Pat::Ident(PatIdent {
+            ident,
+            by_ref: None,
+            subpat: None,
+            ..
+        }) = &*arg.pat
+        else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "component props must be bound to a plain identifier",
+            ));
+        };
+
+        if let Type::ImplTrait(_) = &*arg.ty {
+            return Err(syn::Error::new_spanned(
+                &arg.ty,
+                "component props can't be `impl Trait`, use a generic parameter instead",
+            ));
+        }
+
+        let mut ty = (*arg.ty).clone();
+        let is_slot = into_slot_type(&mut ty);
+        lifetimes.visit_type_mut(&mut ty);
+
+        let attrs = &arg.attrs;
+        let pat = &arg.pat;
+        let field = quote! { #(#attrs)* #vis #ident: #ty };
+
+        if is_slot {
+            // a bare `Slot` could be another type imported under that name.
+            let written = &arg.ty;
+            slot_checks.push(quote_spanned! {written.span()=>
+                let _: &#written = &#ident;
+            });
+            push_lifetimes(&ty, &mut slot_lifetimes);
+            slot_fields.push(field);
+            slot_patterns.push(quote! { #pat });
+        } else {
+            push_lifetimes(&ty, &mut props_lifetimes);
+            fields.push(field);
+            patterns.push(quote! { #pat });
+        }
+    }
+
+    let mut generics = f.sig.generics.clone();
+    if lifetimes.found {
+        generics.params.insert(
+            0,
+            GenericParam::Lifetime(LifetimeParam::new(lifetimes.lifetime)),
+        );
+    }
+
+    // lifetimes only the slots borrow for would be unused by the props struct.
+    let mut props_generics = generics.clone();
+    props_generics.params = generics
+        .params
+        .iter()
+        .filter(|p| match p {
+            GenericParam::Lifetime(l) => props_lifetimes.contains(&l.lifetime),
+            _ => true,
+        })
+        .cloned()
+        .collect();
+    let (impl_generics, ty_generics, where_clause) = props_generics.split_for_impl();
+
+    let doc = format!("The props of the [`{name}`] component.");
+    let props_struct = quote! {
+        #[doc = #doc]
+        #vis struct #props #impl_generics #where_clause {
+            #(#fields),*
+        }
+    };
+
+    let (slots_struct, slots_arg) = if slot_fields.is_empty() {
+        (None, None)
+    } else {
+        let slots = format_ident!("{}Slots", name);
+        let doc = format!("The slots of the [`{name}`] layout, empty unless filled in.");
+
+        (
+            Some(quote! {
+                #[doc = #doc]
+                #[derive(Default)]
+                #vis struct #slots<#(#slot_lifetimes),*> {
+                    #(#slot_fields),*
+                }
+            }),
+            Some(quote! { #slots { #(#slot_patterns),* }: #slots<#(#slot_lifetimes),*> }),
+        )
+    };
+
+    f.sig.inputs = parse_quote! {
+        #props { #(#patterns),* }: #props #ty_generics,
+        #slots_arg
+    };
+    let body = &f.block;
+    f.block = parse_quote! {{
+        #(#slot_checks)*
+        #body
+    }};
+    f.sig.generics = generics;
+    f.attrs.push(parse_quote! { #[allow(non_snake_case)] });
+
+    Ok(quote! {
+        #props_struct
+        #slots_struct
+        #f
+    })
+}
+
+fn generate_struct_component(s: ItemStruct) -> syn::Result<TokenStream> {
+    if !matches!(s.fields, syn::Fields::Named(_)) {
+        return Err(syn::Error::new_spanned(
+            &s.fields,
+            "component structs must have named fields",
+        ));
+    }
+
+    let vis = &s.vis;
+    let name = &s.ident;
+    let props = format_ident!("{}Props", name);
+    let (impl_generics, ty_generics, where_clause) = s.generics.split_for_impl();
+
+    Ok(quote! {
+        #s
+
+        #vis type #props #ty_generics = #name #ty_generics;
+
+        #[allow(non_snake_case)]
+        #vis fn #name #impl_generics (props: #name #ty_generics) -> #name #ty_generics #where_clause {
+            props
+        }
+    })
+}
+
+/// Whether `ty` is an `lfml::Slot`, written as `Slot` or `lfml::Slot`, in which case it's replaced
+/// by `lfml::Slot` with its elided lifetime made explicit as `'_`, so that it gets named like
+/// those of references.
+///
+/// Other paths, like `crate::Slot`, are taken as props: they can't be `lfml::Slot` short of a
+/// re-export, and the generated function checks that a bare `Slot` is `lfml::Slot`.
+fn into_slot_type(ty: &mut Type) -> bool {
+    let Type::Path(p) = ty else {
+        return false;
+    };
+    let segments = &p.path.segments;
+    let is_slot = p.qself.is_none()
+        && segments
+            .iter()
+            .rev()
+            .skip(1)
+            .all(|s| s.arguments.is_empty())
+        && match segments.len() {
+            1 => p.path.leading_colon.is_none() && segments[0].ident == "Slot",
+            2 => segments[0].ident == "lfml" && segments[1].ident == "Slot",
+            _ => false,
+        };
+    if is_slot {
+        let mut last = p.path.segments.last().expect("non-empty path").clone();
+        if last.arguments.is_empty() {
+            last.arguments = PathArguments::AngleBracketed(parse_quote! { <'_> });
+        }
+        *ty = parse_quote! { lfml::#last };
+    }
+    is_slot
+}
+
+/// Pushes the lifetimes in `ty` which aren't in `lifetimes` yet.
+fn push_lifetimes(ty: &Type, lifetimes: &mut Vec<Lifetime>) {
+    struct Lifetimes<'a>(&'a mut Vec<Lifetime>);
+
+    impl Visit<'_> for Lifetimes<'_> {
+        fn visit_lifetime(&mut self, l: &Lifetime) {
+            if !self.0.contains(l) {
+                self.0.push(l.clone());
+            }
+        }
+    }
+
+    Lifetimes(lifetimes).visit_type(ty);
+}
+
+/// Names the elided lifetimes of references, and replaces `'_`, with `lifetime`.
+struct NameElidedLifetimes {
+    lifetime: Lifetime,
+    found: bool,
+}
+
+impl VisitMut for NameElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, r: &mut syn::TypeReference) {
+        if r.lifetime.is_none() {
+            r.lifetime = Some(self.lifetime.clone());
+            self.found = true;
+        }
+        syn::visit_mut::visit_type_reference_mut(self, r);
+    }
+
+    fn visit_lifetime_mut(&mut self, l: &mut Lifetime) {
+        if l.ident == "_" {
+            *l = self.lifetime.clone();
+            self.found = true;
+        }
+    }
+
+    // elided lifetimes in `fn(&str)` and `Fn(&str)` are higher-ranked, and are left alone.
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
@@ -1,15 +1,22 @@
 mod generate;
 mod parse;
 mod syntax;
+mod validate;
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
+use syn::{parse::Parse, Lifetime, Token};
 
-use self::{generate::markup_as_string_push_operations, parse::LfmlParser, syntax::MarkupId};
+use self::{
+    generate::{markup_as_string_push_operations, Sink, TextContext},
+    parse::LfmlParser,
+    syntax::MarkupId,
+    validate::validate_markup,
+};
 
-// TODO: we can return a size hint when we parse the markup maybe?
-const SIZE_MULTIPLIER: usize = 5;
 const OUT_ID: &str = "__lfml_output";
+const WRITE_LABEL: &str = "'__lfml_write";
+const CAPACITY_ID: &str = "__LFML_CAPACITY";
 const UNNAMED_TAG: &str = "div";
 
 fn output_ident() -> Ident {
@@ -24,17 +31,90 @@ pub fn generate_markup_expr(
     input: proc_macro2::TokenStream,
 ) -> syn::Result<proc_macro2::TokenStream> {
     let out_id = output_ident();
-    let size_hint = input.to_string().len() * SIZE_MULTIPLIER;
 
     let ast = LfmlParser(input.into_iter()).collect::<syn::Result<Vec<_>>>()?;
 
+    if cfg!(feature = "strict") {
+        validate_markup(&ast)?;
+    }
+
+    let mut output = TokenStream::new();
+
+    // the literals are sized exactly, the interpolated values reserve their `Render::size_hint` as
+    // they're pushed.
+    let static_len = markup_as_string_push_operations(
+        &Sink::String(output_ident()),
+        TextContext::Html,
+        ast,
+        &mut output,
+    )?;
+
+    if cfg!(feature = "adaptive-capacity") {
+        let capacity_id = Ident::new(CAPACITY_ID, Span::mixed_site());
+
+        Ok(quote! {{
+            static #capacity_id: lfml::Capacity = lfml::Capacity::new(#static_len);
+            let mut #out_id = String::with_capacity(#capacity_id.get());
+            #output
+            #capacity_id.update(#out_id.len());
+            lfml::Escaped::__from_html(#out_id, lfml::__FromHtml::__lfml_macro_only())
+        }})
+    } else {
+        Ok(quote! {{
+            let mut #out_id = String::with_capacity(#static_len);
+            #output
+            lfml::Escaped::__from_html(#out_id, lfml::__FromHtml::__lfml_macro_only())
+        }})
+    }
+}
+
+/// `#writer, #markup`
+struct WriteToInput {
+    writer: syn::Expr,
+    markup: TokenStream,
+}
+
+impl Parse for WriteToInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let writer = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let markup = input.parse()?;
+
+        Ok(WriteToInput { writer, markup })
+    }
+}
+
+pub fn generate_write_to_expr(
+    input: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let WriteToInput { writer, markup } = syn::parse2(input)?;
+
+    let out_id = output_ident();
+    let label = Lifetime::new(WRITE_LABEL, Span::mixed_site());
+
+    let ast = LfmlParser(markup.into_iter()).collect::<syn::Result<Vec<_>>>()?;
+
+    if cfg!(feature = "strict") {
+        validate_markup(&ast)?;
+    }
+
     let mut output = TokenStream::new();
 
-    markup_as_string_push_operations(&output_ident(), ast, &mut output)?;
+    markup_as_string_push_operations(
+        &Sink::Writer {
+            id: output_ident(),
+            label: label.clone(),
+        },
+        TextContext::Html,
+        ast,
+        &mut output,
+    )?;
 
     Ok(quote! {{
-        let mut #out_id = String::with_capacity(#size_hint);
-        #output
-        lfml::Escaped(#out_id)
+        let #out_id = &mut #writer;
+        #label: {
+            #output
+            ::core::fmt::Result::Ok(())
+        }
     }})
 }
@@ -1,188 +1,1064 @@
 use crate::html::syntax::{
-    External, InterpMarkupExpr, InterpValue, InterpValueType, Markup, MarkupId, TagAttribute,
+    Class, ComponentProp, External, InterpMarkupExpr, InterpValue, InterpValueType, Markup,
+    MarkupId, StyleDeclaration, StyleValue, TagAttribute,
 };
 
 use proc_macro2::{Ident, Literal, Span, TokenStream};
-use quote::{quote, TokenStreamExt};
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use syn::Lifetime;
 
+/// The buffer the generated push operations write into.
+#[derive(Debug, Clone)]
+pub enum Sink {
+    /// `html!`: a `String` owned by the generated block.
+    String(Ident),
+    /// `html_to!`: a `&mut impl core::fmt::Write`. A failed write breaks out of the block labelled
+    /// `label` with the error.
+    Writer { id: Ident, label: Lifetime },
+}
+
+impl Sink {
+    fn push_str(&self, s: TokenStream) -> TokenStream {
+        match self {
+            Sink::String(id) => quote! {
+                #id.push_str(#s);
+            },
+            Sink::Writer { id, label } => quote! {
+                if let Err(e) = ::core::fmt::Write::write_str(#id, #s) {
+                    break #label Err(e);
+                }
+            },
+        }
+    }
+
+    /// Pushes the `Display` of `expr`, escaped by the `lfml::escape` module `escaper` as it's
+    /// formatted. The url escaper checks the scheme of the whole url, so the url is formatted into
+    /// a `String` first.
+    fn push_escaped(&self, expr: &External, escaper: &TokenStream) -> TokenStream {
+        if !escapes_by_char(escaper) {
+            return self.push_escaped_str(quote! { &{#expr}.to_string() }, escaper);
+        }
+        match self {
+            Sink::String(id) => {
+                let write = write_escaped(quote! { &mut #id }, expr, escaper);
+                quote! {
+                    let _ = #write;
+                }
+            }
+            Sink::Writer { id, label } => {
+                let write = write_escaped(quote! { &mut *#id }, expr, escaper);
+                quote! {
+                    if let Err(e) = #write {
+                        break #label Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pushes the `&str` expression `s`, escaped by the `lfml::escape` module `escaper`.
+    fn push_escaped_str(&self, s: TokenStream, escaper: &TokenStream) -> TokenStream {
+        match self {
+            Sink::String(id) => quote! {
+                #escaper::escape_to_string(#s, &mut #id);
+            },
+            Sink::Writer { .. } => self.push_str(quote! {
+                &#escaper::escape_string(#s)
+            }),
+        }
+    }
+
+    fn push_render(&self, expr: &External) -> TokenStream {
+        match self {
+            Sink::String(id) => quote! {{
+                let __lfml_value = &{#expr};
+                #id.reserve(lfml::Render::size_hint(__lfml_value));
+                lfml::Render::markup_to_string(__lfml_value, &mut #id);
+            }},
+            Sink::Writer { id, label } => quote! {
+                if let Err(e) = lfml::Render::render_to(&{#expr}, &mut *#id) {
+                    break #label Err(e);
+                }
+            },
+        }
+    }
+}
+
+/// The kind of element the markup being generated is the content of, which decides how text is
+/// escaped.
+#[derive(Debug, Clone, Copy)]
+pub enum TextContext {
+    /// html text, escaped by `lfml::Render`.
+    Html,
+    /// `<script>` body: literals are emitted as is, slots as JSON string literals.
+    Script,
+    /// `<style>` body: literals are emitted as is, slots are escaped as css values.
+    Style,
+}
+
+impl TextContext {
+    fn of(tag: &MarkupId) -> Self {
+        match tag {
+            MarkupId::Basic(t) if t == "script" => TextContext::Script,
+            MarkupId::Basic(t) if t == "style" => TextContext::Style,
+            _ => TextContext::Html,
+        }
+    }
+}
+
+/// The `lfml::escape` module used for the value of the attribute `name`.
+fn attr_escaper(name: &MarkupId) -> TokenStream {
+    if lfml_escape::url::is_url_attribute(&name.to_string()) {
+        url_escaper()
+    } else {
+        quote! { lfml::escape::attr }
+    }
+}
+
+fn url_escaper() -> TokenStream {
+    quote! { lfml::escape::url }
+}
+
+/// Whether `escaper` escapes a char at a time, i.e. has an `escape_to_writer` which a value can be
+/// formatted into.
+fn escapes_by_char(escaper: &TokenStream) -> bool {
+    escaper.to_string() != url_escaper().to_string()
+}
+
+/// The `fmt::Result` of formatting `expr` into the `&mut impl fmt::Write` `output`, escaped by
+/// `escaper` on the way.
+fn write_escaped(output: TokenStream, expr: &External, escaper: &TokenStream) -> TokenStream {
+    quote! {
+        ::core::fmt::Write::write_fmt(
+            &mut lfml::escape::EscapeWriter::new(#output, #escaper::escape_to_writer),
+            ::core::format_args!("{}", { #expr }),
+        )
+    }
+}
+
+/// Binds `slots_id` to the slots argument of a layout, from the `@slot` blocks filling them in its
+/// children.
+///
+/// Each fill becomes a closure rendering into whatever the layout renders into; the slots which
+/// aren't filled are left to their `Default`, i.e. empty.
+fn bind_slots(
+    path: &syn::Path,
+    children: Vec<Markup>,
+    slots_id: &Ident,
+) -> syn::Result<TokenStream> {
+    let mut slots_path = path.clone();
+    let last = slots_path
+        .segments
+        .last_mut()
+        .expect("non-empty component path");
+    let component = last.ident.clone();
+    last.ident = format_ident!("{}Slots", last.ident);
+
+    let writer_id = Ident::new("__lfml_slot_output", Span::mixed_site());
+    let label = Lifetime::new("'__lfml_slot", Span::mixed_site());
+
+    // the closures filling the slots, along with any `@let` bindings they use.
+    let mut statements = vec![];
+    let mut fills = vec![];
+    for child in children {
+        let (name, block) = match child {
+            Markup::NamedSlot { name, block } => (name, block),
+            Markup::Statements(stmts) => {
+                statements.push(quote! { #stmts });
+                continue;
+            }
+            _ => return Err(syn::Error::new(
+                component.span(),
+                format!("the children of `{component}` fill its slots, so they must all be `@slot` blocks or `@let` bindings"),
+            )),
+        };
+
+        let mut render = TokenStream::new();
+        markup_as_string_push_operations(
+            &Sink::Writer {
+                id: writer_id.clone(),
+                label: label.clone(),
+            },
+            TextContext::Html,
+            block.unwrap_or_default(),
+            &mut render,
+        )?;
+
+        let render = if render.is_empty() {
+            quote! { ::core::fmt::Result::Ok(()) }
+        } else {
+            quote! {
+                #label: {
+                    #render
+                    ::core::fmt::Result::Ok(())
+                }
+            }
+        };
+
+        let closure_id = format_ident!("__lfml_fill_{}", name, span = Span::mixed_site());
+        statements.push(quote! {
+            let #closure_id = |#writer_id: &mut dyn ::core::fmt::Write| -> ::core::fmt::Result {
+                #render
+            };
+        });
+        fills.push(quote! { #name: lfml::Slot::new(&#closure_id) });
+    }
+
+    // the update is needless when the page fills every slot.
+    Ok(quote! {
+        #(#statements)*
+        #[allow(clippy::needless_update)]
+        let #slots_id = #slots_path {
+            #(#fills,)*
+            ..::core::default::Default::default()
+        };
+    })
+}
+
+/// Appends to `output` the operations pushing `input` into `sink`, and returns the number of bytes
+/// always pushed, i.e. those of the literals outside of loops and of branches some other branch
+/// doesn't match.
 pub fn markup_as_string_push_operations(
-    buffer_id: &Ident,
+    sink: &Sink,
+    context: TextContext,
     input: Vec<Markup>,
     output: &mut TokenStream,
-) -> syn::Result<()> {
-    for markup in input {
-        match markup {
-            Markup::LiteralSequence(ls) => {
-                let mut lit_concat = String::new();
-                for l in ls {
-                    l.push_to_string(&mut lit_concat)?;
-                }
+) -> syn::Result<usize> {
+    let mut ops = Ops {
+        sink,
+        output: TokenStream::new(),
+        literal: String::new(),
+        static_len: 0,
+    };
+    push_markup(&mut ops, context, input)?;
+    ops.flush();
+
+    output.append_all(ops.output);
+    Ok(ops.static_len)
+}
+
+/// The push operations generated for a sequence of markup, in which consecutive literals, e.g. an
+/// opening tag, its text and its closing tag, are merged into a single push.
+struct Ops<'a> {
+    sink: &'a Sink,
+    output: TokenStream,
+    literal: String,
+    static_len: usize,
+}
+
+impl Ops<'_> {
+    fn push_literal(&mut self, s: &str) {
+        self.literal.push_str(s);
+        self.static_len += s.len();
+    }
+
+    /// Pushes the operations of anything but a literal, after the literals before it.
+    fn push_code(&mut self, code: TokenStream) {
+        self.flush();
+        self.output.append_all(code);
+    }
+
+    fn flush(&mut self) {
+        if !self.literal.is_empty() {
+            let litstr = Literal::string(&std::mem::take(&mut self.literal));
+            self.output
+                .append_all(self.sink.push_str(quote! { #litstr }));
+        }
+    }
+}
+
+/// Pushes `input` into the rust block of its parent, unless it has `@let` bindings, which then
+/// get a block of their own so that they end with `input`.
+fn push_block(ops: &mut Ops, context: TextContext, input: Vec<Markup>) -> syn::Result<()> {
+    if !input.iter().any(|m| matches!(m, Markup::Statements(_))) {
+        return push_markup(ops, context, input);
+    }
 
-                let litstr = Literal::string(&lit_concat);
+    let mut block = TokenStream::new();
+    let static_len = markup_as_string_push_operations(ops.sink, context, input, &mut block)?;
+    ops.push_code(quote! {{ #block }});
+    ops.static_len += static_len;
+    Ok(())
+}
 
-                output.append_all(quote! {
-                    #buffer_id.push_str(#litstr);
+/// Pushes an attribute of an opening tag, the interpolated values being escaped straight into the
+/// output.
+fn push_attr(ops: &mut Ops, attr: TagAttribute) -> syn::Result<()> {
+    let sink = ops.sink;
+    match attr {
+        TagAttribute::Lit { name, value } => {
+            let mut lit = format!(" {name}");
+            if let Some(v) = value {
+                lit.push_str("=\"");
+                v.push_to_string(&mut lit)?;
+                lit.push('"');
+            }
+            ops.push_literal(&lit);
+        }
+        TagAttribute::Interpolated { value, r#type } => match r#type {
+            InterpValue::Toggle { name } => {
+                let push = sink.push_str(Literal::string(&format!(" {name}")).into_token_stream());
+                ops.push_code(quote! {
+                    if { #value } {
+                        #push
+                    }
                 });
             }
-            Markup::Tag { tag, attrs, inner } => {
-                let mut opening_tag = String::from("<");
-                let mut interp_attrs = vec![];
-
-                opening_tag.push_str(&tag.to_string());
-
-                for attr in attrs {
-                    match attr {
-                        TagAttribute::Lit { name, value } => {
-                            opening_tag.push(' ');
-                            opening_tag.push_str(&name.to_string());
-                            if let Some(v) = value {
-                                opening_tag.push_str("=\"");
-                                v.push_to_string(&mut opening_tag)?;
-                                opening_tag.push('\"');
+            InterpValue::NameValue { name, wrapper } => {
+                let escaper = attr_escaper(&name);
+                match wrapper {
+                    InterpValueType::None => {
+                        ops.push_literal(&format!(" {name}=\""));
+                        ops.push_code(sink.push_escaped(&value, &escaper));
+                        ops.push_literal("\"");
+                    }
+                    InterpValueType::Option => {
+                        let value_id = Ident::new("__lfml_value", Span::mixed_site());
+                        let open = sink
+                            .push_str(Literal::string(&format!(" {name}=\"")).into_token_stream());
+                        let push = sink.push_escaped(&External(quote! { #value_id }), &escaper);
+                        let close = sink.push_str(quote! { "\"" });
+                        ops.push_code(quote! {
+                            if let Some(#value_id) = { #value } {
+                                #open
+                                #push
+                                #close
                             }
+                        });
+                    }
+                }
+            }
+            InterpValue::Spread { .. } => {
+                unreachable!("the attributes of a tag with a spread are pushed by `push_attr_set`")
+            }
+        },
+        TagAttribute::Block { name, value } => {
+            let raw_id = Ident::new("__lfml_attr", Span::mixed_site());
+            let pushes = attr_block_pushes(&raw_id, value)?;
+            let push = sink.push_escaped_str(quote! { &#raw_id }, &attr_escaper(&name));
+            ops.push_literal(&format!(" {name}=\""));
+            ops.push_code(quote! {{
+                let mut #raw_id = String::new();
+                #(#pushes)*
+                #push
+            }});
+            ops.push_literal("\"");
+        }
+        TagAttribute::Classes(classes) => push_classes(ops, classes, true)?,
+        TagAttribute::Style(declarations) => {
+            if declarations
+                .iter()
+                .all(|d| matches!(d.value, StyleValue::Lit(_)))
+            {
+                let mut style = String::new();
+                for (i, d) in declarations.into_iter().enumerate() {
+                    let StyleValue::Lit(l) = d.value else {
+                        unreachable!("only literal declarations are left");
+                    };
+                    let mut value = String::new();
+                    l.push_raw_to_string(&mut value)?;
+                    if i > 0 {
+                        style.push_str("; ");
+                    }
+                    lfml_escape::css_value::escape_to_string(&d.property, &mut style);
+                    style.push_str(": ");
+                    lfml_escape::css_value::escape_to_string(&value, &mut style);
+                }
+                ops.push_literal(&format!(
+                    " style=\"{}\"",
+                    lfml_escape::attr::escape_string(&style)
+                ));
+            } else {
+                let style_id = Ident::new("__lfml_style", Span::mixed_site());
+                let pushes = style_pushes(&style_id, declarations)?;
+                let open = sink.push_str(quote! { " style=\"" });
+                let push = sink.push_escaped(
+                    &External(quote! { #style_id }),
+                    &quote! { lfml::escape::attr },
+                );
+                let close = sink.push_str(quote! { "\"" });
+                ops.push_code(quote! {{
+                    let mut #style_id = lfml::Style::new();
+                    #(#pushes)*
+                    if !#style_id.is_empty() {
+                        #open
+                        #push
+                        #close
+                    }
+                }});
+            }
+        }
+        // only tags with a spread resolve conflicts, see `push_attr_set`.
+        TagAttribute::Conflicts(_) => {}
+    }
+    Ok(())
+}
+
+/// Renders the slot `expr` like `Sink::push_render`, warning if it's an `lfml::PreEscaped`.
+///
+/// The warning is the deprecation of `PreEscaped::__lfml_audit_raw`, which takes precedence over
+/// the method of the same name that `lfml::__AuditRaw` gives every type, and is spanned to `expr`.
+fn audit_raw(sink: &Sink, expr: &External) -> TokenStream {
+    let value_id = Ident::new("__lfml_raw", Span::mixed_site());
+    let span = expr
+        .0
+        .clone()
+        .into_iter()
+        .next()
+        .map_or_else(Span::call_site, |t| t.span());
+    let audit = Ident::new("__lfml_audit_raw", span);
+    let render = sink.push_render(&External(quote! { #value_id }));
+    quote! {{
+        use lfml::__AuditRaw as _;
+        let #value_id = { #expr };
+        #value_id.#audit();
+        #render
+    }}
+}
+
+/// The pushes building the value of an attribute block into the `String` `raw_id`.
+///
+/// The value is built up unescaped, then escaped as a whole, so that e.g. the scheme of a url is
+/// checked on the complete value.
+fn attr_block_pushes(raw_id: &Ident, value: Vec<Markup>) -> syn::Result<Vec<TokenStream>> {
+    let mut pushes = Vec::new();
+    for m in value {
+        match m {
+            Markup::LiteralSequence(l) => {
+                let mut raw = String::new();
+                for lit in l {
+                    lit.push_raw_to_string(&mut raw)?;
+                }
+                pushes.push(quote! { #raw_id.push_str(#raw); });
+            }
+            Markup::Slot(InterpMarkupExpr::Simple(s)) => {
+                pushes.push(quote! {
+                    let _ = ::core::fmt::Write::write_fmt(&mut #raw_id, ::core::format_args!("{}", { #s }));
+                });
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    Span::mixed_site(),
+                    "attribute block syntax only supports literals and simple slots",
+                ))
+            }
+        }
+    }
+    Ok(pushes)
+}
+
+/// The pushes setting the `declarations` on the `lfml::Style` `style_id`.
+fn style_pushes(
+    style_id: &Ident,
+    declarations: Vec<StyleDeclaration>,
+) -> syn::Result<Vec<TokenStream>> {
+    let mut pushes = Vec::new();
+    for StyleDeclaration {
+        property, value, ..
+    } in declarations
+    {
+        pushes.push(match value {
+            StyleValue::Lit(l) => {
+                let mut raw = String::new();
+                l.push_raw_to_string(&mut raw)?;
+                quote! { #style_id.push(#property, #raw); }
+            }
+            StyleValue::Interpolated {
+                value,
+                wrapper: InterpValueType::None,
+            } => quote! { #style_id.push(#property, { #value }); },
+            StyleValue::Interpolated {
+                value,
+                wrapper: InterpValueType::Option,
+            } => quote! { #style_id.push_option(#property, { #value }); },
+        });
+    }
+    Ok(pushes)
+}
+
+/// Errors on an attribute written twice on the same tag, as browsers silently drop the second one.
+fn check_duplicate_attrs(attrs: &[TagAttribute]) -> syn::Result<()> {
+    let mut seen: Vec<String> = vec![];
+    let mut policy = None;
+    for attr in attrs {
+        let (name, span) = match attr {
+            TagAttribute::Lit { name, .. }
+            | TagAttribute::Block { name, .. }
+            | TagAttribute::Interpolated {
+                r#type: InterpValue::Toggle { name } | InterpValue::NameValue { name, .. },
+                ..
+            } => (name.to_string().to_ascii_lowercase(), name.span()),
+            TagAttribute::Classes(classes) => ("class".to_string(), classes[0].span()),
+            TagAttribute::Style(declarations) => {
+                let mut properties: Vec<&str> = vec![];
+                for d in declarations {
+                    if properties.contains(&d.property.as_str()) {
+                        return Err(syn::Error::new(
+                            d.span,
+                            format!("the property `{}` is set twice", d.property),
+                        ));
+                    }
+                    properties.push(&d.property);
+                }
+                let span = declarations
+                    .first()
+                    .map_or_else(Span::call_site, |d| d.span);
+                ("style".to_string(), span)
+            }
+            TagAttribute::Conflicts(p) => {
+                if policy.replace(p).is_some() {
+                    return Err(syn::Error::new(p.span(), "`@conflicts` is set twice"));
+                }
+                continue;
+            }
+            TagAttribute::Interpolated {
+                r#type: InterpValue::Spread { .. },
+                ..
+            } => continue,
+        };
+
+        if seen.contains(&name) {
+            let hint = if name == "class" {
+                ", use only class shorthands such as `.a` or `.(expr)`, which are merged into one"
+            } else {
+                ""
+            };
+            return Err(syn::Error::new(
+                span,
+                format!("the attribute `{name}` is set twice{hint}"),
+            ));
+        }
+        seen.push(name);
+    }
+    Ok(())
+}
+
+/// Pushes the attributes of a tag with a spread. They're collected in an `lfml::Attributes`, which
+/// resolves the conflicts between the spread and the other attributes, and then written. The
+/// spreads add their attributes to it directly, as its `lfml::AttributeSink`.
+///
+/// A spread which is the only attribute of its tag, without `@conflicts`, has nothing to conflict
+/// with, so it's written straight into the output instead.
+fn push_attr_set(ops: &mut Ops, attrs: Vec<TagAttribute>) -> syn::Result<()> {
+    let sink = ops.sink;
+    let set_id = Ident::new("__lfml_attrs", Span::mixed_site());
+    let value_id = Ident::new("__lfml_value", Span::mixed_site());
+
+    if let [TagAttribute::Interpolated {
+        value,
+        r#type:
+            InterpValue::Spread {
+                tag: MarkupId::Basic(tag),
+                wrapper,
+            },
+    }] = &attrs[..]
+    {
+        let spread = match wrapper {
+            InterpValueType::None => quote! {
+                { &#value }.__lfml_tags().#tag(#set_id);
+            },
+            InterpValueType::Option => quote! {
+                if let Some(#value_id) = { &#value } {
+                    #value_id.__lfml_tags().#tag(#set_id);
+                }
+            },
+        };
+        ops.push_code(match sink {
+            Sink::String(id) => quote! {{
+                let #set_id = &mut #id;
+                #spread
+            }},
+            Sink::Writer { id, label } => quote! {{
+                let mut #set_id = lfml::AttributeWriter::new(&mut *#id);
+                {
+                    let #set_id = &mut #set_id;
+                    #spread
+                }
+                if let Err(e) = #set_id.finish() {
+                    break #label Err(e);
+                }
+            }},
+        });
+        return Ok(());
+    }
+
+    let policy = attrs
+        .iter()
+        .find_map(|a| match a {
+            TagAttribute::Conflicts(p) => {
+                let variant = p
+                    .to_string()
+                    .split('_')
+                    .map(|w| w[..1].to_ascii_uppercase() + &w[1..])
+                    .collect::<String>();
+                Some(Ident::new(&variant, p.span()))
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| Ident::new("Merge", Span::mixed_site()));
+
+    let mut pushes = vec![];
+    for attr in attrs {
+        pushes.push(match attr {
+            TagAttribute::Lit { name, value } => {
+                let name = name.to_string();
+                match value {
+                    Some(v) => {
+                        let mut lit = String::new();
+                        v.push_to_string(&mut lit)?;
+                        quote! { #set_id.push(#name, Some(#lit.into())); }
+                    }
+                    None => quote! { #set_id.push(#name, None); },
+                }
+            }
+            TagAttribute::Interpolated { value, r#type } => match r#type {
+                InterpValue::Toggle { name } => {
+                    let name = name.to_string();
+                    quote! {
+                        if { #value } {
+                            #set_id.push(#name, None);
                         }
-                        TagAttribute::Interpolated { value, r#type } => match r#type {
-                            InterpValue::Toggle { name } => {
-                                let litstr = Literal::string(&format!(" {name}"));
-                                interp_attrs.push(quote! {
-                                    if { #value } {
-                                        #litstr
-                                    } else {
-                                        ""
-                                    }
-                                });
-
-                                opening_tag.push_str("{}");
-                            }
-                            InterpValue::NameValue { name, wrapper } => match wrapper {
-                                InterpValueType::None => {
-                                    interp_attrs.push(quote! {
-                                        lfml::escape_string(&{
-                                            #value
-                                        }.to_string())
-                                    });
-                                    opening_tag.push_str(&format!(" {}=\"{{}}\"", name));
-                                }
-                                InterpValueType::Option => {
-                                    let litstr = Literal::string(&format!(" {}=\"{{}}\"", name));
-
-                                    interp_attrs.push(quote! {
-                                        if let Some(e) = { #value } {
-                                            format!(#litstr, e)
-                                        } else {
-                                            "".into()
-                                        }
-                                    });
-
-                                    opening_tag.push_str("{}");
-                                }
-                            },
-                            InterpValue::Spread { tag, wrapper } => {
-                                let MarkupId::Basic(tag) = tag else {
-                                    todo!("spreading for tags containing hyphens");
-                                };
-                                match wrapper {
-                                    InterpValueType::None => {
-                                        interp_attrs.push(quote! { {
-                                            { &#value }.__lfml_tags().#tag()
-                                        }});
-                                    }
-                                    InterpValueType::Option => {
-                                        interp_attrs.push(quote! { {
-                                            if let Some(i) = { &#value } {
-                                                i.__lfml_tags().#tag()
-                                            } else {
-                                                "".into()
-                                            }
-                                        }});
-                                    }
-                                }
-
-                                opening_tag.push_str("{}");
+                    }
+                }
+                // `lfml::Attributes` checks the schemes of urls, once they're escaped.
+                InterpValue::NameValue { name, wrapper } => {
+                    let name = name.to_string();
+                    match wrapper {
+                        InterpValueType::None => quote! {
+                            #set_id.push_fmt(
+                                #name,
+                                ::core::format_args!("{}", { #value }),
+                                lfml::escape::attr::escape_to_writer,
+                            );
+                        },
+                        InterpValueType::Option => quote! {
+                            if let Some(#value_id) = { #value } {
+                                #set_id.push_fmt(
+                                    #name,
+                                    ::core::format_args!("{}", #value_id),
+                                    lfml::escape::attr::escape_to_writer,
+                                );
                             }
                         },
-                        TagAttribute::Block { name, value } => {
-                            let mut fmt = String::new();
-                            let mut args: Vec<External> = Vec::new();
-                            for m in value {
-                                match m {
-                                    Markup::LiteralSequence(l) => {
-                                        for lit in l {
-                                            lit.push_to_string(&mut fmt)?;
-                                        }
-                                    },
-                                    Markup::Slot(InterpMarkupExpr::Simple(s)) => {
-                                        fmt.push_str("{}");
-                                        args.push(s);
-                                    }
-                                    _ => return Err(syn::Error::new(
-                                            Span::mixed_site(),
-                                            "attribute block syntax only supports literals and simple slots",
-                                    )),
-                                }
+                    }
+                }
+                InterpValue::Spread { tag, wrapper } => {
+                    let MarkupId::Basic(tag) = tag else {
+                        return Err(syn::Error::new(
+                            tag.span(),
+                            format!("can't spread onto `<{tag}>`, spreading is only supported for html5 tags"),
+                        ));
+                    };
+                    match wrapper {
+                        InterpValueType::None => quote! {
+                            { &#value }.__lfml_tags().#tag(&mut #set_id);
+                        },
+                        InterpValueType::Option => quote! {
+                            if let Some(#value_id) = { &#value } {
+                                #value_id.__lfml_tags().#tag(&mut #set_id);
                             }
-                            interp_attrs.push(quote! {
-                                format!(#fmt, #(#args),*)
-                            });
-                            opening_tag.push_str(&format!(" {}=\"{{}}\"", name));
-                            // todo!("ok {name}=format!(\"{fmt}\", {args:?})");
-                        }
+                        },
                     }
                 }
+            },
+            TagAttribute::Block { name, value } => {
+                let pushes = attr_block_pushes(&value_id, value)?;
+                let name = name.to_string();
+                quote! {{
+                    let mut #value_id = String::new();
+                    #(#pushes)*
+                    #set_id.push_fmt(
+                        #name,
+                        ::core::format_args!("{}", #value_id),
+                        lfml::escape::attr::escape_to_writer,
+                    );
+                }}
+            }
+            TagAttribute::Classes(classes) => {
+                let class_id = Ident::new("__lfml_class", Span::mixed_site());
+                let always = classes.iter().any(|c| !c.is_option());
+                let mut class = Ops {
+                    sink: &Sink::String(class_id.clone()),
+                    output: TokenStream::new(),
+                    literal: String::new(),
+                    static_len: 0,
+                };
+                push_classes(&mut class, classes, false)?;
+                class.flush();
+                let class = class.output;
+                let push = quote! { #set_id.push("class", Some(#class_id.into())); };
+                let push = if always {
+                    push
+                } else {
+                    quote! {
+                        if !#class_id.is_empty() {
+                            #push
+                        }
+                    }
+                };
+                quote! {{
+                    let mut #class_id = String::new();
+                    #class
+                    #push
+                }}
+            }
+            TagAttribute::Style(declarations) => {
+                let style_id = Ident::new("__lfml_style", Span::mixed_site());
+                let pushes = style_pushes(&style_id, declarations)?;
+                quote! {{
+                    let mut #style_id = lfml::Style::new();
+                    #(#pushes)*
+                    if !#style_id.is_empty() {
+                        #set_id.push_fmt(
+                            "style",
+                            ::core::format_args!("{}", #style_id),
+                            lfml::escape::attr::escape_to_writer,
+                        );
+                    }
+                }}
+            }
+            TagAttribute::Conflicts(_) => continue,
+        });
+    }
 
-                opening_tag.push('>');
+    let render = sink.push_render(&External(quote! { &#set_id }));
+    ops.push_code(quote! {{
+        let mut #set_id = lfml::Attributes::new(lfml::Conflicts::#policy);
+        #(#pushes)*
+        #render
+    }});
+    Ok(())
+}
 
-                let open = Literal::string(&opening_tag);
+/// Pushes the class shorthands of a tag as a single `class` attribute, the classes separated by
+/// spaces. The attribute is left out when every class is an `Option` which is `None`.
+///
+/// Without `attribute`, only the value of the attribute is pushed.
+fn push_classes(ops: &mut Ops, classes: Vec<Class>, attribute: bool) -> syn::Result<()> {
+    /// Whether a class was pushed before the one being generated.
+    #[derive(Clone, Copy, PartialEq)]
+    enum Pushed {
+        No,
+        Yes,
+        /// Only if one of the `Option`s before it was `Some`, kept track of at runtime.
+        Maybe,
+    }
 
-                output.append_all(if interp_attrs.is_empty() {
-                    quote! {
-                        #buffer_id.push_str(#open);
+    let sink = ops.sink;
+    let escaper = quote! { lfml::escape::attr };
+    let pushed_id = Ident::new("__lfml_class_pushed", Span::mixed_site());
+    let value_id = Ident::new("__lfml_value", Span::mixed_site());
+
+    let always = classes.iter().any(|c| !c.is_option());
+    if always && attribute {
+        ops.push_literal(" class=\"");
+    }
+
+    let open = if always || !attribute {
+        TokenStream::new()
+    } else {
+        sink.push_str(quote! { " class=\"" })
+    };
+    let space = sink.push_str(quote! { " " });
+
+    let mut pushed = Pushed::No;
+    let mut declared = false;
+    let count = classes.len();
+    for (i, class) in classes.into_iter().enumerate() {
+        if !class.is_option() {
+            match pushed {
+                Pushed::No => {}
+                Pushed::Yes => ops.push_literal(" "),
+                Pushed::Maybe => ops.push_code(quote! {
+                    if #pushed_id {
+                        #space
                     }
+                }),
+            }
+            match class {
+                Class::Lit(l) => {
+                    let mut lit = String::new();
+                    l.push_to_string(&mut lit)?;
+                    ops.push_literal(&lit);
+                }
+                Class::Interpolated { value, .. } => {
+                    ops.push_code(sink.push_escaped(&value, &escaper));
+                }
+            }
+            pushed = Pushed::Yes;
+            continue;
+        }
+
+        let Class::Interpolated { value, .. } = class else {
+            unreachable!("only interpolated classes are options");
+        };
+        let separator = match pushed {
+            Pushed::No => open.clone(),
+            Pushed::Yes => space.clone(),
+            Pushed::Maybe => quote! {
+                if #pushed_id {
+                    #space
                 } else {
-                    quote! {
-                        #buffer_id.push_str(
-                            &format!(#open, #(#interp_attrs),*)
-                        );
+                    #open
+                }
+            },
+        };
+        // the flag is only read by the classes after this one, and to close the attribute.
+        let set_pushed = if pushed != Pushed::Yes && (i + 1 < count || (attribute && !always)) {
+            if !declared {
+                ops.push_code(quote! { let mut #pushed_id = false; });
+                declared = true;
+            }
+            quote! { #pushed_id = true; }
+        } else {
+            TokenStream::new()
+        };
+        let push = sink.push_escaped(&External(quote! { #value_id }), &escaper);
+        ops.push_code(quote! {
+            if let Some(#value_id) = { #value } {
+                #separator
+                #push
+                #set_pushed
+            }
+        });
+        if pushed == Pushed::No {
+            pushed = Pushed::Maybe;
+        }
+    }
+
+    if always && attribute {
+        ops.push_literal("\"");
+    } else if attribute {
+        let close = sink.push_str(quote! { "\"" });
+        ops.push_code(quote! {
+            if #pushed_id {
+                #close
+            }
+        });
+    }
+    Ok(())
+}
+
+fn push_markup(ops: &mut Ops, context: TextContext, input: Vec<Markup>) -> syn::Result<()> {
+    let sink = ops.sink;
+    for markup in input {
+        match markup {
+            Markup::LiteralSequence(ls) => {
+                let mut lit_concat = String::new();
+                for l in ls {
+                    match context {
+                        TextContext::Html => l.push_to_string(&mut lit_concat)?,
+                        TextContext::Script | TextContext::Style => {
+                            l.push_raw_to_string(&mut lit_concat)?
+                        }
                     }
+                }
+
+                ops.push_literal(&lit_concat);
+            }
+            Markup::Tag { tag, attrs, inner } => {
+                let is_void = matches!(tag, MarkupId::Basic(_))
+                    && lfml_html5::is_void_element(&tag.to_string());
+
+                if is_void && inner.is_some() {
+                    return Err(syn::Error::new(
+                        tag.span(),
+                        format!(
+                            "`<{tag}>` is a void element and cannot have children, use `{tag};`"
+                        ),
+                    ));
+                }
+
+                check_duplicate_attrs(&attrs)?;
+                let has_spread = attrs.iter().any(|a| {
+                    matches!(
+                        a,
+                        TagAttribute::Interpolated {
+                            r#type: InterpValue::Spread { .. },
+                            ..
+                        }
+                    )
                 });
 
+                ops.push_literal(&format!("<{tag}"));
+                if has_spread {
+                    push_attr_set(ops, attrs)?;
+                } else {
+                    if let Some(TagAttribute::Conflicts(p)) = attrs
+                        .iter()
+                        .find(|a| matches!(a, TagAttribute::Conflicts(_)))
+                    {
+                        return Err(syn::Error::new(
+                            p.span(),
+                            "`@conflicts` only applies to tags with a spread `@(...)`",
+                        ));
+                    }
+                    for attr in attrs {
+                        push_attr(ops, attr)?;
+                    }
+                }
+                ops.push_literal(">");
+
                 if let Some(inner) = inner {
-                    markup_as_string_push_operations(buffer_id, inner, output)?;
+                    push_block(ops, TextContext::of(&tag), inner)?;
+                }
 
-                    let close = Literal::string(&format!("</{}>", tag));
-                    output.append_all(quote! {
-                        #buffer_id.push_str(#close);
-                    })
+                if !is_void {
+                    ops.push_literal(&format!("</{tag}>"));
                 }
             }
-            Markup::AnonBlock(b) => {
-                markup_as_string_push_operations(buffer_id, b, output)?;
+            Markup::AnonBlock(b) => push_block(ops, context, b)?,
+            Markup::Statements(stmts) => ops.push_code(quote! { #stmts }),
+            Markup::Component {
+                path,
+                props,
+                mut children,
+            } => {
+                // the props are passed as a struct literal, so that rustc reports missing and
+                // unknown props against the props struct generated by `#[component]`.
+                let mut props_path = path.clone();
+                let last = props_path
+                    .segments
+                    .last_mut()
+                    .expect("non-empty component path");
+                last.ident = format_ident!("{}Props", last.ident);
+
+                let mut fields = props
+                    .iter()
+                    .map(|ComponentProp { name, value }| quote! { #name: { #value } })
+                    .collect::<Vec<_>>();
+
+                let fills_slots = children
+                    .iter()
+                    .flatten()
+                    .any(|c| matches!(c, Markup::NamedSlot { .. }));
+
+                let slots_id = Ident::new("__lfml_slots", Span::mixed_site());
+                let slots = if fills_slots {
+                    Some(bind_slots(
+                        &path,
+                        children.take().unwrap_or_default(),
+                        &slots_id,
+                    )?)
+                } else {
+                    None
+                };
+
+                if let Some(children) = children {
+                    let children_id = Ident::new("__lfml_children", Span::mixed_site());
+                    let mut children_expr = TokenStream::new();
+                    markup_as_string_push_operations(
+                        &Sink::String(children_id.clone()),
+                        TextContext::Html,
+                        children,
+                        &mut children_expr,
+                    )?;
+                    let children_field = Ident::new("children", last.ident.span());
+                    fields.push(quote! {
+                        #children_field: {
+                            let mut #children_id = String::new();
+                            #children_expr
+                            lfml::Escaped::__from_html(#children_id, lfml::__FromHtml::__lfml_macro_only())
+                        }
+                    });
+                }
+
+                let props = quote! { #props_path { #(#fields),* } };
+                // only layouts take a slots argument, which is left to its default when the page
+                // fills none of their slots.
+                let call = if slots.is_some() {
+                    quote! { #path(#props, #slots_id) }
+                } else {
+                    quote! { lfml::__call_component(#path, #props) }
+                };
+                let render = sink.push_render(&External(call));
+                ops.push_code(quote! {{
+                    #slots
+                    #render
+                }});
+            }
+            Markup::NamedSlot { name, block } => {
+                let render = sink.push_render(&External(quote! { #name }));
+
+                ops.push_code(match block {
+                    Some(block) => {
+                        let mut default = TokenStream::new();
+                        markup_as_string_push_operations(sink, context, block, &mut default)?;
+                        quote! {
+                            if lfml::Slot::is_filled(&#name) {
+                                #render
+                            } else {
+                                #default
+                            }
+                        }
+                    }
+                    None => render,
+                });
             }
             Markup::Slot(InterpMarkupExpr::Simple(s)) => {
-                output.append_all(quote! {
-                    #buffer_id.push_str(&lfml::Render::markup(&{#s}).as_string());
+                ops.push_code(match context {
+                    TextContext::Html if cfg!(feature = "audit-raw") => audit_raw(sink, &s),
+                    TextContext::Html => sink.push_render(&s),
+                    // always a string, so that the value can't be taken as code.
+                    TextContext::Script => {
+                        let quote = sink.push_str(quote! { "\"" });
+                        let push = sink.push_escaped(&s, &quote! { lfml::escape::script });
+                        quote! {
+                            #quote
+                            #push
+                            #quote
+                        }
+                    }
+                    TextContext::Style => {
+                        sink.push_escaped(&s, &quote! { lfml::escape::css_value })
+                    }
                 });
             }
             Markup::Slot(InterpMarkupExpr::Match(outer, variants)) => {
                 let mut vars = vec![];
+                let mut arms_len = None::<usize>;
                 for (pattern, value) in variants {
                     let mut value_expr = TokenStream::new();
-                    markup_as_string_push_operations(buffer_id, value, &mut value_expr)?;
+                    let len =
+                        markup_as_string_push_operations(sink, context, value, &mut value_expr)?;
+                    arms_len = Some(arms_len.map_or(len, |l| l.min(len)));
 
                     vars.push(quote! {
                         #pattern => { #value_expr }
                     });
                 }
-                output.append_all(quote! {
+                ops.static_len += arms_len.unwrap_or_default();
+                ops.push_code(quote! {
                     #outer {
                         #(#vars)*
                     }
                 });
             }
-            Markup::Slot(InterpMarkupExpr::For(outer, repeat_block)) => {
+            Markup::Slot(InterpMarkupExpr::For {
+                pattern,
+                iter,
+                context: loop_context,
+                body,
+            }) => {
                 let mut value_expr = TokenStream::new();
-                markup_as_string_push_operations(buffer_id, repeat_block, &mut value_expr)?;
-                output.append_all(quote! {
+                markup_as_string_push_operations(sink, context, body, &mut value_expr)?;
+                ops.push_code(match loop_context {
+                    Some(loop_context) => quote! {
+                        for (#loop_context, #pattern) in lfml::looped(#iter) {
+                            #value_expr
+                        }
+                    },
+                    None => quote! {
+                        for #pattern in #iter {
+                            #value_expr
+                        }
+                    },
+                });
+            }
+            Markup::Slot(InterpMarkupExpr::While(outer, repeat_block)) => {
+                let mut value_expr = TokenStream::new();
+                markup_as_string_push_operations(sink, context, repeat_block, &mut value_expr)?;
+                ops.push_code(quote! {
                     #outer {
                         #value_expr
                     }
@@ -193,11 +1069,22 @@ pub fn markup_as_string_push_operations(
                 else_blocks,
             }) => {
                 let mut if_value_expr = TokenStream::new();
-                markup_as_string_push_operations(buffer_id, if_value, &mut if_value_expr)?;
+                let mut branches_len =
+                    markup_as_string_push_operations(sink, context, if_value, &mut if_value_expr)?;
+                // without a final `@else`, no branch may be taken at all.
+                let exhaustive = else_blocks
+                    .last()
+                    .is_some_and(|(e, _)| e.0.clone().into_iter().count() == 1);
                 let mut elses = vec![];
                 for (else_block, else_value) in else_blocks {
                     let mut else_value_expr = TokenStream::new();
-                    markup_as_string_push_operations(buffer_id, else_value, &mut else_value_expr)?;
+                    let len = markup_as_string_push_operations(
+                        sink,
+                        context,
+                        else_value,
+                        &mut else_value_expr,
+                    )?;
+                    branches_len = branches_len.min(len);
 
                     elses.push(quote! {
                         #else_block {
@@ -206,7 +1093,10 @@ pub fn markup_as_string_push_operations(
                     });
                 }
 
-                output.append_all(quote! {
+                if exhaustive {
+                    ops.static_len += branches_len;
+                }
+                ops.push_code(quote! {
                     #if_expr {
                         #if_value_expr
                     }
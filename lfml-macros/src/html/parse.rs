@@ -1,8 +1,9 @@
 use crate::html::syntax::{
-    InterpMarkupExpr, InterpValue, InterpValueType, Markup, MarkupId, MarkupLit, TagAttribute,
+    Class, ComponentProp, InterpMarkupExpr, InterpValue, InterpValueType, Markup, MarkupId,
+    MarkupLit, StyleDeclaration, StyleValue, TagAttribute,
 };
 
-use proc_macro2::{Delimiter, Ident, Literal, Span, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Group, Ident, Literal, Spacing, Span, TokenStream, TokenTree};
 use quote::ToTokens;
 use syn::Lit;
 
@@ -34,6 +35,11 @@ impl Iterator for LfmlParser {
 
                     return Some(Ok(Markup::LiteralSequence(ls)));
                 }
+                Some(TokenTree::Ident(_)) if self.at_component() => {
+                    let component = result_to_option!(self.parse_component());
+
+                    return Some(Ok(component));
+                }
                 Some(TokenTree::Ident(_)) => {
                     let ident = result_to_option!(self.parse_ident());
 
@@ -76,7 +82,12 @@ impl Iterator for LfmlParser {
                         match self.peek_2() {
                             (_, Some(TokenTree::Ident(i)))
                                 if p.as_char() == '@'
-                                    && (i == "match" || i == "if" || i == "for") =>
+                                    && (i == "match"
+                                        || i == "if"
+                                        || i == "for"
+                                        || i == "slot"
+                                        || i == "let"
+                                        || i == "while") =>
                             {
                                 self.advance();
                                 if i == "match" {
@@ -91,11 +102,45 @@ impl Iterator for LfmlParser {
                                     let expr = result_to_option!(self.parse_if());
 
                                     return Some(Ok(Markup::Slot(expr)));
+                                } else if i == "slot" {
+                                    let slot = result_to_option!(self.parse_named_slot());
+
+                                    return Some(Ok(slot));
+                                } else if i == "while" {
+                                    let expr = result_to_option!(self.parse_while());
+
+                                    return Some(Ok(Markup::Slot(expr)));
+                                } else if i == "let" {
+                                    let stmt = result_to_option!(self.parse_let());
+
+                                    return Some(Ok(Markup::Statements(stmt)));
                                 }
                             }
-                            _ => {
-                                // todo!("{t:?}");
+                            (_, Some(TokenTree::Group(g)))
+                                if p.as_char() == '@' && g.delimiter() == Delimiter::Brace =>
+                            {
+                                self.advance_2();
+
+                                return Some(Ok(Markup::Statements(External(g.stream()))));
+                            }
+                            (_, Some(TokenTree::Ident(i))) if p.as_char() == '@' => {
+                                let msg = if i == "else" {
+                                    "`@else` must directly follow the block of an `@if`".to_string()
+                                } else if let Some(k) = did_you_mean(&i.to_string(), KEYWORDS) {
+                                    format!("unknown `@{i}`, did you mean `@{k}`?")
+                                } else {
+                                    format!(
+                                        "unknown `@{i}`, expected one of {}",
+                                        KEYWORDS
+                                            .iter()
+                                            .map(|k| format!("`@{k}`"))
+                                            .collect::<Vec<_>>()
+                                            .join(", ")
+                                    )
+                                };
+                                return Some(Err(syn::Error::new(i.span(), msg)));
                             }
+                            _ => {}
                         }
                         let tag = unnamed_tag_ident();
 
@@ -119,12 +164,20 @@ impl Iterator for LfmlParser {
                     ';' => {
                         self.advance();
                     }
-                    _ => todo!(),
+                    c => {
+                        return Some(Err(syn::Error::new(
+                            p.span(),
+                            format!(
+                                "unexpected `{c}`, expected a tag, a literal, a `(slot)`, \
+                                 a `{{ block }}` or an `@` keyword"
+                            ),
+                        )))
+                    }
                 },
                 Some(TokenTree::Group(g)) => {
                     return Some(Err(syn::Error::new(
                         g.span(),
-                        "interpolating Option<impl Render> values ",
+                        "unexpected `[...]`, did you mean to interpolate it with `(...)`?",
                     )));
                 }
                 None => return None,
@@ -168,6 +221,116 @@ impl LfmlParser {
         self.0.next();
     }
 
+    /// Components are told apart from html tags by a capitalised name, or by a path.
+    fn at_component(&self) -> bool {
+        match self.peek_2() {
+            (Some(TokenTree::Ident(i)), _) if i.to_string().starts_with(char::is_uppercase) => true,
+            (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(p))) => p.as_char() == ':',
+            _ => false,
+        }
+    }
+
+    fn parse_component(&mut self) -> syn::Result<Markup> {
+        let mut path = TokenStream::new();
+        loop {
+            match self.peek_3() {
+                (
+                    Some(TokenTree::Ident(i)),
+                    Some(TokenTree::Punct(c0)),
+                    Some(TokenTree::Punct(c1)),
+                ) if c0.as_char() == ':' && c1.as_char() == ':' => {
+                    path.extend([TokenTree::Ident(i), c0.into(), c1.into()]);
+                    self.advance_3();
+                }
+                (Some(TokenTree::Ident(i)), _, _) => {
+                    i.to_tokens(&mut path);
+                    self.advance();
+                    break;
+                }
+                (t, _, _) => {
+                    return Err(syn::Error::new(
+                        t.map(|t| t.span()).unwrap_or(Span::call_site()),
+                        "expected the name of a component",
+                    ))
+                }
+            }
+        }
+        let path: syn::Path = syn::parse2(path)?;
+
+        let mut props = vec![];
+        loop {
+            match self.peek_2() {
+                (Some(TokenTree::Punct(p)), _) if p.as_char() == ';' => {
+                    self.advance();
+                    return Ok(Markup::Component {
+                        path,
+                        props,
+                        children: None,
+                    });
+                }
+                (Some(TokenTree::Group(g)), _) if g.delimiter() == Delimiter::Brace => {
+                    self.advance();
+                    let children = Self(g.stream().into_iter()).collect::<syn::Result<_>>()?;
+                    return Ok(Markup::Component {
+                        path,
+                        props,
+                        children: Some(children),
+                    });
+                }
+                (None, _) => {
+                    return Ok(Markup::Component {
+                        path,
+                        props,
+                        children: None,
+                    })
+                }
+                (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(eq)))
+                    if eq.as_char() == '=' =>
+                {
+                    self.advance_2();
+
+                    let value = match self.peek_2() {
+                        (Some(TokenTree::Group(g)), _) if g.delimiter() == Delimiter::Parenthesis => {
+                            self.advance();
+                            g.stream()
+                        }
+                        (Some(TokenTree::Literal(l)), _) => {
+                            self.advance();
+                            l.to_token_stream()
+                        }
+                        (Some(TokenTree::Ident(b)), _) if b == "true" || b == "false" => {
+                            self.advance();
+                            b.to_token_stream()
+                        }
+                        (Some(TokenTree::Punct(m)), Some(TokenTree::Literal(l)))
+                            if m.as_char() == '-' =>
+                        {
+                            self.advance_2();
+                            TokenStream::from_iter([TokenTree::Punct(m), l.into()])
+                        }
+                        (t, _) => {
+                            return Err(syn::Error::new(
+                                t.map(|t| t.span()).unwrap_or(eq.span()),
+                                "expected a literal or a parenthesised expression as the value of a prop",
+                            ))
+                        }
+                    };
+
+                    props.push(ComponentProp {
+                        name,
+                        value: External(value),
+                    });
+                }
+                (Some(t), _) => {
+                    return Err(syn::Error::new(
+                        t.span(),
+                        "expected a prop `name=value`, `;` or a block of children",
+                    ))
+                }
+            }
+        }
+    }
+
     fn parse_literal_seq(&mut self) -> syn::Result<Vec<MarkupLit>> {
         let mut inner = vec![];
 
@@ -245,6 +408,34 @@ impl LfmlParser {
         Ok(InterpMarkupExpr::Match(External(outer_ext), variants))
     }
 
+    /// Collects the head of an `if`, `else` or `while` block into `head`, returning its body.
+    ///
+    /// The body is the first brace group outside of a `let` pattern, so that e.g. the braces of
+    /// `let A { a } = x` stay in the head.
+    fn parse_block_head(&mut self, kw: &Ident, head: &mut TokenStream) -> syn::Result<Group> {
+        let mut in_pattern = false;
+        let mut after_joint = false;
+        loop {
+            let t = self.advance();
+            match &t {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace && !in_pattern => {
+                    return Ok(g.clone());
+                }
+                Some(TokenTree::Ident(i)) if i == "let" => in_pattern = true,
+                // the `=` ending the pattern, rather than part of `==`, `<=`, `..=` etc.
+                Some(TokenTree::Punct(p))
+                    if p.as_char() == '=' && p.spacing() == Spacing::Alone && !after_joint =>
+                {
+                    in_pattern = false
+                }
+                Some(_) => {}
+                None => return Err(syn::Error::new(kw.span(), "unexpected end of macro")),
+            }
+            after_joint = matches!(&t, Some(TokenTree::Punct(p)) if p.spacing() == Spacing::Joint);
+            t.to_tokens(head);
+        }
+    }
+
     fn parse_if(&mut self) -> syn::Result<InterpMarkupExpr> {
         let if_kw = match self.advance() {
             Some(TokenTree::Ident(i)) if i == "if" => i,
@@ -257,65 +448,50 @@ impl LfmlParser {
         };
 
         let mut outer_ext = if_kw.to_token_stream();
-        let outer_markup: Vec<Markup>;
-        let mut else_blocks = vec![];
-        'outer: loop {
-            match self.peek() {
-                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
-                    self.advance();
-                    outer_markup = Self(g.stream().into_iter()).collect::<syn::Result<Vec<_>>>()?;
+        let body = self.parse_block_head(&if_kw, &mut outer_ext)?;
+        let outer_markup = Self(body.stream().into_iter()).collect::<syn::Result<Vec<_>>>()?;
 
-                    'test_if: loop {
-                        match self.peek_2() {
-                            (Some(TokenTree::Punct(p)), Some(TokenTree::Ident(else_kw)))
-                                if p.as_char() == '@' && else_kw == "else" =>
-                            {
-                                let mut else_ext = else_kw.to_token_stream();
-                                self.advance_2();
-                                loop {
-                                    match self.peek() {
-                                        Some(TokenTree::Group(g))
-                                            if g.delimiter() == Delimiter::Brace =>
-                                        {
-                                            self.advance();
-                                            let else_markup = Self(g.stream().into_iter())
-                                                .collect::<syn::Result<Vec<_>>>()?;
-
-                                            else_blocks
-                                                .push((External(else_ext.clone()), else_markup));
-
-                                            continue 'test_if;
-                                        }
-                                        Some(t) => {
-                                            t.to_tokens(&mut else_ext);
-                                            self.advance();
-                                        }
-                                        None => {
-                                            return Err(syn::Error::new(
-                                                if_kw.span(),
-                                                "unexpected end of macro",
-                                            ))
-                                        }
-                                    }
-                                }
-                            }
-                            _ => break 'outer,
-                        }
-                    }
-                }
-                Some(t) => {
-                    t.to_tokens(&mut outer_ext);
-                    self.advance();
-                }
-                None => return Err(syn::Error::new(if_kw.span(), "unexpected end of macro")),
+        let mut else_blocks = vec![];
+        while let (Some(TokenTree::Punct(p)), Some(TokenTree::Ident(else_kw))) = self.peek_2() {
+            if p.as_char() != '@' || else_kw != "else" {
+                break;
             }
+            self.advance_2();
+
+            let mut else_ext = else_kw.to_token_stream();
+            let body = self.parse_block_head(&else_kw, &mut else_ext)?;
+            let else_markup = Self(body.stream().into_iter()).collect::<syn::Result<Vec<_>>>()?;
+
+            else_blocks.push((External(else_ext), else_markup));
         }
+
         Ok(InterpMarkupExpr::If {
             if_block: (External(outer_ext), outer_markup),
             else_blocks,
         })
     }
 
+    fn parse_while(&mut self) -> syn::Result<InterpMarkupExpr> {
+        let while_kw = match self.advance() {
+            Some(TokenTree::Ident(i)) if i == "while" => i,
+            t => {
+                return Err(syn::Error::new(
+                    t.map(|t| t.span()).unwrap_or(Span::mixed_site()),
+                    "expected `while` ident",
+                ))
+            }
+        };
+
+        let mut outer_ext = while_kw.to_token_stream();
+        let body = self.parse_block_head(&while_kw, &mut outer_ext)?;
+        let repeating_blocks = Self(body.stream().into_iter()).collect::<syn::Result<_>>()?;
+
+        Ok(InterpMarkupExpr::While(
+            External(outer_ext),
+            repeating_blocks,
+        ))
+    }
+
     fn parse_for(&mut self) -> syn::Result<InterpMarkupExpr> {
         let for_kw = match self.advance() {
             Some(TokenTree::Ident(i)) if i == "for" => i,
@@ -326,41 +502,202 @@ impl LfmlParser {
                 ))
             }
         };
-        let mut outer_ext = for_kw.to_token_stream();
-        let mut in_kw = None;
-        let repeating_blocks;
+
+        let mut pattern = TokenStream::new();
+        let mut context = None;
         loop {
-            match self.peek() {
-                Some(TokenTree::Group(g))
-                    if g.delimiter() == Delimiter::Brace && in_kw.is_some() =>
-                {
-                    self.advance();
+            match self.advance() {
+                Some(TokenTree::Ident(i)) if i == "in" => break,
+                // `for #context, #pattern in`: a top level comma can't be part of a pattern.
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' && context.is_none() => {
+                    let ctx = syn::parse2::<Ident>(std::mem::take(&mut pattern)).map_err(|_| {
+                        syn::Error::new(
+                            p.span(),
+                            "expected a single identifier for the loop context before `,`",
+                        )
+                    })?;
+                    context = Some(ctx);
+                }
+                Some(t) => t.to_tokens(&mut pattern),
+                None => return Err(syn::Error::new(for_kw.span(), "unexpected end of macro")),
+            }
+        }
 
-                    repeating_blocks = match Self(g.stream().into_iter()).collect() {
-                        Ok(ms) => ms,
-                        Err(e) => return Err(e),
-                    };
+        let mut iter = TokenStream::new();
+        let body = loop {
+            match self.advance() {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => break g,
+                Some(t) => t.to_tokens(&mut iter),
+                None => return Err(syn::Error::new(for_kw.span(), "unexpected end of macro")),
+            }
+        };
+        let body = Self(body.stream().into_iter()).collect::<syn::Result<_>>()?;
 
+        Ok(InterpMarkupExpr::For {
+            pattern: External(pattern),
+            iter: External(iter),
+            context,
+            body,
+        })
+    }
+
+    fn parse_let(&mut self) -> syn::Result<External> {
+        let let_kw = match self.advance() {
+            Some(TokenTree::Ident(i)) if i == "let" => i,
+            t => {
+                return Err(syn::Error::new(
+                    t.map(|t| t.span()).unwrap_or(Span::mixed_site()),
+                    "expected `let` ident",
+                ))
+            }
+        };
+        let mut stmt = let_kw.to_token_stream();
+        loop {
+            match self.advance() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ';' => {
+                    p.to_tokens(&mut stmt);
+                    return Ok(External(stmt));
+                }
+                Some(t) => t.to_tokens(&mut stmt),
+                None => {
+                    return Err(syn::Error::new(
+                        let_kw.span(),
+                        "expected `;` to end the `@let` binding",
+                    ))
+                }
+            }
+        }
+    }
+
+    fn parse_named_slot(&mut self) -> syn::Result<Markup> {
+        let slot_kw = match self.advance() {
+            Some(TokenTree::Ident(i)) if i == "slot" => i,
+            t => {
+                return Err(syn::Error::new(
+                    t.map(|t| t.span()).unwrap_or(Span::mixed_site()),
+                    "expected `slot` ident",
+                ))
+            }
+        };
+        let name = match self.advance() {
+            Some(TokenTree::Ident(i)) => i,
+            t => {
+                return Err(syn::Error::new(
+                    t.map(|t| t.span()).unwrap_or(slot_kw.span()),
+                    "expected the name of the slot after `@slot`",
+                ))
+            }
+        };
+
+        let block = match self.peek() {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                self.advance();
+                Some(Self(g.stream().into_iter()).collect::<syn::Result<_>>()?)
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => {
+                self.advance();
+                None
+            }
+            None => None,
+            Some(t) => {
+                return Err(syn::Error::new(
+                    t.span(),
+                    "expected `;` or a block after the name of the slot",
+                ))
+            }
+        };
+
+        Ok(Markup::NamedSlot { name, block })
+    }
+
+    /// Parses the declarations of `style { ... }`, i.e. `#property: #value`, separated by `;`.
+    fn parse_style(&mut self) -> syn::Result<Vec<StyleDeclaration>> {
+        let mut output = vec![];
+        while let Some(first) = self.peek() {
+            let span = first.span();
+
+            // custom properties start with `--`.
+            let mut property = String::new();
+            while let Some(TokenTree::Punct(p)) = self.peek() {
+                if p.as_char() != '-' {
                     break;
                 }
-                Some(TokenTree::Ident(i)) if i == "in" => {
-                    in_kw.replace(i.clone());
-                    i.to_tokens(&mut outer_ext);
+                property.push('-');
+                self.advance();
+            }
+            if !matches!(self.peek(), Some(TokenTree::Ident(_))) {
+                return Err(syn::Error::new(
+                    span,
+                    "expected a property, e.g. `color: \"red\"`",
+                ));
+            }
+            property.push_str(&self.parse_ident()?.to_string());
+
+            match self.advance() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ':' => {}
+                t => {
+                    return Err(syn::Error::new(
+                        t.map_or(span, |t| t.span()),
+                        format!("expected `:` after the property `{property}`"),
+                    ))
+                }
+            }
+
+            let value = match self.peek() {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
                     self.advance();
+                    StyleValue::Interpolated {
+                        value: External(g.stream()),
+                        wrapper: InterpValueType::None,
+                    }
                 }
-                Some(t) => {
-                    t.to_tokens(&mut outer_ext);
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => {
                     self.advance();
+                    StyleValue::Interpolated {
+                        value: External(g.stream()),
+                        wrapper: InterpValueType::Option,
+                    }
+                }
+                t => match self.parse_literal()? {
+                    Some(l) => StyleValue::Lit(l),
+                    None => {
+                        return Err(syn::Error::new(
+                            t.map_or(span, |t| t.span()),
+                            format!("expected a value for `{property}`, e.g. `\"red\"`, `(expr)` or `[option]`"),
+                        ))
+                    }
+                },
+            };
+
+            match self.advance() {
+                None => {}
+                Some(TokenTree::Punct(p)) if p.as_char() == ';' => {}
+                Some(t) => {
+                    return Err(syn::Error::new(
+                        t.span(),
+                        format!("expected `;` after the value of `{property}`"),
+                    ))
                 }
-                None => return Err(syn::Error::new(for_kw.span(), "unexpected end of macro")),
             }
+
+            output.push(StyleDeclaration {
+                property,
+                span,
+                value,
+            });
         }
-        Ok(InterpMarkupExpr::For(External(outer_ext), repeating_blocks))
+        Ok(output)
     }
 
     fn parse_ident(&mut self) -> syn::Result<MarkupId> {
-        let Some(TokenTree::Ident(i)) = self.advance() else {
-            unreachable!()
+        let i = match self.advance() {
+            Some(TokenTree::Ident(i)) => i,
+            t => {
+                return Err(syn::Error::new(
+                    t.map(|t| t.span()).unwrap_or(Span::call_site()),
+                    "expected an identifier",
+                ))
+            }
         };
 
         let mut thr = String::new();
@@ -422,9 +759,37 @@ impl LfmlParser {
         }
     }
 
+    /// Parses the attributes of `tag`, merging its class shorthands, and then its content if it has
+    /// any.
     fn parse_attrs(
         &mut self,
         tag: MarkupId,
+    ) -> syn::Result<(Vec<TagAttribute>, Option<TokenStream>)> {
+        let (attrs, inner) = self.parse_attr_list(tag)?;
+
+        let mut output: Vec<TagAttribute> = Vec::with_capacity(attrs.len());
+        let mut classes_index = None;
+        for attr in attrs {
+            match (attr, classes_index) {
+                (TagAttribute::Classes(c), Some(i)) => {
+                    if let TagAttribute::Classes(classes) = &mut output[i] {
+                        classes.extend(c);
+                    }
+                }
+                (TagAttribute::Classes(c), None) => {
+                    classes_index = Some(output.len());
+                    output.push(TagAttribute::Classes(c));
+                }
+                (attr, _) => output.push(attr),
+            }
+        }
+
+        Ok((output, inner))
+    }
+
+    fn parse_attr_list(
+        &mut self,
+        tag: MarkupId,
     ) -> syn::Result<(Vec<TagAttribute>, Option<TokenStream>)> {
         let mut output = vec![];
         loop {
@@ -440,7 +805,7 @@ impl LfmlParser {
                 (Some(TokenTree::Ident(_)), _) => {
                     let ident = self.parse_ident()?;
 
-                    'attr: loop {
+                    'attr: {
                         match self.peek_2() {
                             (Some(TokenTree::Punct(p)), Some(TokenTree::Group(g)))
                                 if p.as_char() == '='
@@ -530,6 +895,17 @@ impl LfmlParser {
                                 self.advance();
                                 break 'attr;
                             }
+                            (Some(TokenTree::Group(g)), _)
+                                if g.delimiter() == Delimiter::Brace
+                                    && ident.to_string() == "style"
+                                    && is_style_block(g.stream()) =>
+                            {
+                                let declarations =
+                                    LfmlParser(g.stream().into_iter()).parse_style()?;
+                                output.push(TagAttribute::Style(declarations));
+                                self.advance();
+                                break 'attr;
+                            }
                             (Some(TokenTree::Group(g)), _) if g.delimiter() == Delimiter::Brace => {
                                 output.push(TagAttribute::Lit {
                                     name: ident.clone(),
@@ -556,7 +932,32 @@ impl LfmlParser {
                                 });
                                 break 'attr;
                             }
-                            t => todo!("unexpected token whilst parsing attr {t:?}"),
+                            (Some(TokenTree::Literal(l)), _) => {
+                                return Err(syn::Error::new(
+                                    l.span(),
+                                    format!("expected `=` between the attribute and its value, did you mean `{ident}={l}`?"),
+                                ))
+                            }
+                            (Some(TokenTree::Group(g)), _)
+                                if g.delimiter() == Delimiter::Parenthesis =>
+                            {
+                                return Err(syn::Error::new(
+                                    g.span(),
+                                    format!("expected `=` between the attribute and its value, did you mean `{ident}=(...)`?"),
+                                ))
+                            }
+                            (Some(t), _) => {
+                                return Err(syn::Error::new(
+                                    t.span(),
+                                    format!("unexpected `{t}` after the attribute `{ident}`"),
+                                ))
+                            }
+                            (None, _) => {
+                                return Err(syn::Error::new(
+                                    ident.span(),
+                                    format!("expected `;` or a `{{ ... }}` block to end the tag `{tag}`, after the attribute `{ident}`"),
+                                ))
+                            }
                         }
                     }
                 }
@@ -564,20 +965,14 @@ impl LfmlParser {
                     if p.as_char() == '.' || p.as_char() == '#' || p.as_char() == '@' =>
                 {
                     output.push(match (p.as_char(), g.delimiter()) {
-                        ('.', Delimiter::Parenthesis) => TagAttribute::Interpolated {
+                        ('.', Delimiter::Parenthesis) => TagAttribute::Classes(vec![Class::Interpolated {
                             value: External(g.stream()),
-                            r#type: InterpValue::NameValue {
-                                name: MarkupId::Basic(Ident::new("class", p.span())),
-                                wrapper: InterpValueType::None,
-                            },
-                        },
-                        ('.', Delimiter::Bracket) => TagAttribute::Interpolated {
+                            wrapper: InterpValueType::None,
+                        }]),
+                        ('.', Delimiter::Bracket) => TagAttribute::Classes(vec![Class::Interpolated {
                             value: External(g.stream()),
-                            r#type: InterpValue::NameValue {
-                                name: MarkupId::Basic(Ident::new("class", p.span())),
-                                wrapper: InterpValueType::Option,
-                            },
-                        },
+                            wrapper: InterpValueType::Option,
+                        }]),
                         ('#', Delimiter::Parenthesis) => TagAttribute::Interpolated {
                             value: External(g.stream()),
                             r#type: InterpValue::NameValue {
@@ -606,45 +1001,161 @@ impl LfmlParser {
                                 wrapper: InterpValueType::Option,
                             },
                         },
-                        _ => {
-                            return Err(syn::Error::new(
-                                p.span(),
-                                format!("{p:?} {g:?} unhandled"),
-                            ));
+                        (c, _) => {
+                            let expected = match c {
+                                '.' => "a class after `.`, e.g. `.foo`, `.\"foo\"` or `.(expr)`",
+                                '#' => "an id after `#`, e.g. `#foo`, `#\"foo\"` or `#(expr)`",
+                                _ => "`(expr)` or `[expr]` to spread after `@`",
+                            };
+                            return Err(syn::Error::new(p.span(), format!("expected {expected}")));
                         }
                     });
                     self.advance_2();
                 }
+                (Some(TokenTree::Punct(p)), Some(TokenTree::Ident(i)))
+                    if p.as_char() == '@' && i == "conflicts" =>
+                {
+                    self.advance_2();
+                    let policy = match self.advance() {
+                        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                            syn::parse2::<Ident>(g.stream()).ok().filter(|p| {
+                                CONFLICT_POLICIES.contains(&p.to_string().as_str())
+                            })
+                        }
+                        _ => None,
+                    };
+                    let Some(policy) = policy else {
+                        return Err(syn::Error::new(
+                            i.span(),
+                            "expected `@conflicts(error)`, `@conflicts(last_wins)` or `@conflicts(merge)`",
+                        ));
+                    };
+                    output.push(TagAttribute::Conflicts(policy));
+                }
                 (Some(TokenTree::Punct(p)), Some(TokenTree::Ident(_)))
                     if p.as_char() == '.' || p.as_char() == '#' =>
                 {
                     self.advance();
 
                     let i = self.parse_ident()?;
+                    let mut lit = Literal::string(&i.to_string());
+                    lit.set_span(i.span());
+                    let value = MarkupLit::Basic(lit);
 
-                    let attr_name = if p.as_char() == '.' { "class" } else { "id" };
-
-                    output.push(TagAttribute::Lit {
-                        name: MarkupId::Basic(Ident::new(attr_name, p.span())),
-                        value: Some(MarkupLit::Basic(Literal::string(&i.to_string()))),
+                    output.push(if p.as_char() == '.' {
+                        TagAttribute::Classes(vec![Class::Lit(value)])
+                    } else {
+                        TagAttribute::Lit {
+                            name: MarkupId::Basic(Ident::new("id", p.span())),
+                            value: Some(value),
+                        }
                     });
                 }
                 (Some(TokenTree::Punct(p)), _) if p.as_char() == '.' || p.as_char() == '#' => {
                     self.advance();
+                    let attr_name = if p.as_char() == '.' { "class" } else { "id" };
                     let Some(l) = self.parse_literal()? else {
                         return Err(syn::Error::new(
                             p.span(),
-                            format!("unable to parse literal {}", p.as_char()),
+                            format!(
+                                "expected {attr_name} after `{}`, e.g. `{}foo`, `{}\"foo\"` or `{}(expr)`",
+                                p.as_char(),
+                                p.as_char(),
+                                p.as_char(),
+                                p.as_char(),
+                            ),
                         ));
                     };
-                    let attr_name = if p.as_char() == '.' { "class" } else { "id" };
-                    output.push(TagAttribute::Lit {
-                        name: MarkupId::Basic(Ident::new(attr_name, p.span())),
-                        value: Some(l),
+                    output.push(if p.as_char() == '.' {
+                        TagAttribute::Classes(vec![Class::Lit(l)])
+                    } else {
+                        TagAttribute::Lit {
+                            name: MarkupId::Basic(Ident::new(attr_name, p.span())),
+                            value: Some(l),
+                        }
                     })
                 }
-                t => todo!("{t:?}"),
+                (Some(TokenTree::Literal(l)), _) => {
+                    return Err(syn::Error::new(
+                        l.span(),
+                        format!("the content of a tag goes in a block, did you mean `{tag} {{ {l} }}`?"),
+                    ))
+                }
+                (Some(TokenTree::Group(g)), _) if g.delimiter() == Delimiter::Parenthesis => {
+                    return Err(syn::Error::new(
+                        g.span(),
+                        format!(
+                            "the content of a tag goes in a block, did you mean `{tag} {{ (...) }}`?"
+                        ),
+                    ))
+                }
+                (Some(t), _) => {
+                    return Err(syn::Error::new(
+                        t.span(),
+                        format!("unexpected `{t}` in the attributes of the tag `{tag}`"),
+                    ))
+                }
+                (None, _) => {
+                    return Err(syn::Error::new(
+                        tag.span(),
+                        format!("expected `;` or a `{{ ... }}` block to end the tag `{tag}`"),
+                    ))
+                }
             }
         }
     }
 }
+
+/// Whether the block after a `style` attribute holds declarations, i.e. starts with `#property:`,
+/// rather than the content of the tag.
+fn is_style_block(block: TokenStream) -> bool {
+    let mut tokens = block
+        .into_iter()
+        .skip_while(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '-'));
+    let mut expect_ident = true;
+    for t in tokens.by_ref() {
+        match t {
+            TokenTree::Ident(_) if expect_ident => expect_ident = false,
+            TokenTree::Punct(p) if p.as_char() == '-' && !expect_ident => expect_ident = true,
+            TokenTree::Punct(p) => return p.as_char() == ':' && p.spacing() == Spacing::Alone,
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// The keywords which can follow `@` in markup.
+const KEYWORDS: &[&str] = &["if", "else", "for", "while", "match", "let", "slot"];
+
+/// The arguments of `@conflicts(...)`, the `lfml::Conflicts` variants in snake case.
+const CONFLICT_POLICIES: &[&str] = &["error", "last_wins", "merge"];
+
+/// The candidate closest to `word`, if it's close enough to be a likely typo.
+fn did_you_mean<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|c| (edit_distance(word, c), *c))
+        .filter(|&(d, c)| d > 0 && d <= c.len().div_ceil(2).min(2))
+        .min_by_key(|&(d, _)| d)
+        .map(|(_, c)| c)
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(cur).min(row[j])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
@@ -20,6 +20,41 @@ pub enum Markup {
     AnonBlock(Vec<Markup>),
     /// lfml::Markup-valued rust expression that is interpolated into the rendered template.
     Slot(InterpMarkupExpr),
+    /// A call to a `#[component]`, recognised by its capitalised name
+    /// ```ignore
+    /// #path #(#prop=#value)* { #children }
+    /// ```
+    Component {
+        path: syn::Path,
+        props: Vec<ComponentProp>,
+        children: Option<Vec<Markup>>,
+    },
+    /// A named slot of a layout. In the children of a component it fills the slot, anywhere else
+    /// it places the `lfml::Slot` named `#name`, with `#block` rendered when it's empty
+    /// ```ignore
+    /// @slot #name { #block }
+    /// // or
+    /// @slot #name;
+    /// ```
+    NamedSlot {
+        name: proc_macro2::Ident,
+        block: Option<Vec<Markup>>,
+    },
+    /// rust statements, spliced in before the markup which follows them so that their bindings
+    /// are in scope for the rest of the enclosing block
+    /// ```ignore
+    /// @let #pat = #expr;
+    /// // or
+    /// @{ #(#stmt)* }
+    /// ```
+    Statements(External),
+}
+
+/// A named prop passed to a component, e.g. `title="x"` or `count=(n + 1)`.
+#[derive(Debug, Clone)]
+pub struct ComponentProp {
+    pub name: proc_macro2::Ident,
+    pub value: External,
 }
 
 #[derive(Debug, Clone)]
@@ -45,11 +80,23 @@ pub enum InterpMarkupExpr {
         else_blocks: Vec<(External, Vec<Markup>)>,
     },
     /// ```ignore
-    /// @#for_expr {
+    /// @for #(#context,)? #pattern in #iter {
+    ///     #markup_expr
+    /// }
+    /// ```
+    /// `#context` is bound to the `lfml::Loop` of each iteration.
+    For {
+        pattern: External,
+        iter: External,
+        context: Option<proc_macro2::Ident>,
+        body: Vec<Markup>,
+    },
+    /// ```ignore
+    /// @#while_expr {
     ///     #markup_expr
     /// }
     /// ```
-    For(External, Vec<Markup>),
+    While(External, Vec<Markup>),
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +114,48 @@ pub enum TagAttribute {
     },
     /// A block containing only LiteralSequence and Slots
     Block { name: MarkupId, value: Vec<Markup> },
+    /// The class shorthands of a tag, merged into a single `class` attribute
+    /// ```ignore
+    /// .#name ."#lit" .(#expr) .[#option_expr]
+    /// ```
+    Classes(Vec<Class>),
+    /// How a spread conflicting with the other attributes of the tag is resolved
+    /// ```ignore
+    /// @conflicts(#policy)
+    /// ```
+    Conflicts(proc_macro2::Ident),
+    /// The declarations of a `style` attribute, those given a `None` option being left out
+    /// ```ignore
+    /// style { #(#property: #value;)* }
+    /// ```
+    Style(Vec<StyleDeclaration>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Class {
+    Lit(MarkupLit),
+    Interpolated {
+        value: External,
+        wrapper: InterpValueType,
+    },
+}
+
+/// A declaration of a `style` attribute, e.g. `color: "red"`, `color: (color)` or
+/// `color: [option]`.
+#[derive(Debug, Clone)]
+pub struct StyleDeclaration {
+    pub property: String,
+    pub span: Span,
+    pub value: StyleValue,
+}
+
+#[derive(Debug, Clone)]
+pub enum StyleValue {
+    Lit(MarkupLit),
+    Interpolated {
+        value: External,
+        wrapper: InterpValueType,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -156,6 +245,39 @@ impl core::fmt::Display for MarkupId {
     }
 }
 
+impl MarkupId {
+    pub fn span(&self) -> Span {
+        match self {
+            MarkupId::Basic(b) => b.span(),
+            MarkupId::Complex(b, _) => b.span(),
+        }
+    }
+}
+
+impl Class {
+    pub fn is_option(&self) -> bool {
+        matches!(
+            self,
+            Class::Interpolated {
+                wrapper: InterpValueType::Option,
+                ..
+            }
+        )
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Class::Lit(l) => l.span(),
+            Class::Interpolated { value, .. } => value
+                .0
+                .clone()
+                .into_iter()
+                .next()
+                .map_or_else(Span::call_site, |t| t.span()),
+        }
+    }
+}
+
 impl MarkupLit {
     pub fn span(&self) -> Span {
         match self {
@@ -165,33 +287,44 @@ impl MarkupLit {
     }
 
     pub fn push_to_string(&self, buf: &mut String) -> syn::Result<()> {
+        self.push_to_string_with(buf, lfml_escape::escape_to_string)
+    }
+
+    /// Pushes the literal without any escaping, for contexts which aren't html (e.g. `<script>`
+    /// bodies), or whose escaping happens later.
+    pub fn push_raw_to_string(&self, buf: &mut String) -> syn::Result<()> {
+        self.push_to_string_with(buf, |s, buf| buf.push_str(s))
+    }
+
+    fn push_to_string_with(
+        &self,
+        buf: &mut String,
+        escape: fn(&str, &mut String),
+    ) -> syn::Result<()> {
         match self {
             MarkupLit::Basic(l) => {
                 match Lit::new(l.clone()) {
                     Lit::Str(s) => {
-                        lfml_escape::escape_to_string(&s.value(), buf);
+                        escape(&s.value(), buf);
                     }
                     Lit::ByteStr(bs) => {
-                        lfml_escape::escape_to_string(&String::from_utf8_lossy(&bs.value()), buf);
+                        escape(&String::from_utf8_lossy(&bs.value()), buf);
                     }
                     Lit::Byte(b) => {
-                        lfml_escape::escape_to_string(&String::from(b.value() as char), buf);
+                        escape(&String::from(b.value() as char), buf);
                     }
                     Lit::Char(c) => {
-                        lfml_escape::escape_to_string(&String::from(c.value()), buf);
+                        escape(&String::from(c.value()), buf);
                     }
                     Lit::Int(i) => {
-                        let i = i.base10_digits().parse::<usize>().expect("parsing litint");
+                        let i = i.base10_parse::<u128>()?;
                         buf.push_str(&i.to_string());
                     }
                     Lit::Float(lf) => {
-                        let lf = lf.base10_digits().parse::<f64>().expect("parsing float");
+                        let lf = lf.base10_parse::<f64>()?;
                         buf.push_str(&lf.to_string());
                     }
                     Lit::Bool(lb) => {
-                        eprintln!(
-                            "true and false are parsed as identifiers, not literal booleans!"
-                        );
                         buf.push_str(&lb.value.to_string());
                     }
                     Lit::Verbatim(v) => {
@@ -209,7 +342,7 @@ impl MarkupLit {
                 Lit::Str(s) => {
                     buf.push_str(&format!("-{}", s.value()));
                 }
-                _ => unreachable!(),
+                _ => return Err(syn::Error::new(self.span(), "expected a number after `-`")),
             },
         };
         Ok(())
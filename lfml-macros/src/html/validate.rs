@@ -0,0 +1,266 @@
+use crate::html::syntax::{InterpMarkupExpr, InterpValue, Markup, MarkupId, TagAttribute};
+
+use lfml_html5::{ContentModel, Element};
+
+/// Checks the parsed markup against the HTML5 element table in `lfml_html5`: unknown tags,
+/// attributes which aren't allowed on an element and illegal nesting are all reported.
+///
+/// Custom elements (names containing a hyphen) are accepted with any attributes, and markup at
+/// the root of the template is accepted anywhere, since it may be interpolated into another
+/// template.
+pub fn validate_markup(markup: &[Markup]) -> syn::Result<()> {
+    let mut errors = None;
+    validate_children(None, markup, &mut errors);
+
+    match errors {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// The element the markup currently being validated sits in.
+#[derive(Clone, Copy)]
+struct Parent<'a> {
+    /// Name of the direct parent tag.
+    name: &'a str,
+    /// The closest ancestor whose content model constrains the children, i.e. the direct parent,
+    /// or the one above it if the direct parent is transparent.
+    element: Option<&'static Element>,
+}
+
+fn push_error(errors: &mut Option<syn::Error>, error: syn::Error) {
+    match errors {
+        Some(e) => e.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+fn validate_children(parent: Option<Parent>, markup: &[Markup], errors: &mut Option<syn::Error>) {
+    for m in markup {
+        match m {
+            Markup::LiteralSequence(_) => {}
+            Markup::Tag { tag, attrs, inner } => {
+                validate_tag(parent, tag, attrs, inner.as_deref(), errors);
+            }
+            Markup::AnonBlock(b) => validate_children(parent, b, errors),
+            // the children are interpolated wherever the component puts them.
+            Markup::Component {
+                children: Some(children),
+                ..
+            } => validate_children(None, children, errors),
+            Markup::Component { children: None, .. } => {}
+            Markup::NamedSlot {
+                block: Some(block), ..
+            } => validate_children(parent, block, errors),
+            Markup::NamedSlot { block: None, .. } | Markup::Statements(_) => {}
+            Markup::Slot(InterpMarkupExpr::Simple(_)) => {}
+            Markup::Slot(InterpMarkupExpr::Match(_, variants)) => {
+                for (_, v) in variants {
+                    validate_children(parent, v, errors);
+                }
+            }
+            Markup::Slot(InterpMarkupExpr::If {
+                if_block: (_, if_value),
+                else_blocks,
+            }) => {
+                validate_children(parent, if_value, errors);
+                for (_, else_value) in else_blocks {
+                    validate_children(parent, else_value, errors);
+                }
+            }
+            Markup::Slot(InterpMarkupExpr::For { body, .. }) => {
+                validate_children(parent, body, errors)
+            }
+            Markup::Slot(InterpMarkupExpr::While(_, b)) => validate_children(parent, b, errors),
+        }
+    }
+}
+
+fn validate_tag(
+    parent: Option<Parent>,
+    tag: &MarkupId,
+    attrs: &[TagAttribute],
+    inner: Option<&[Markup]>,
+    errors: &mut Option<syn::Error>,
+) {
+    let name = tag.to_string();
+
+    if name.contains('-') {
+        if let Some(inner) = inner {
+            let parent = Parent {
+                name: &name,
+                element: None,
+            };
+            validate_children(Some(parent), inner, errors);
+        }
+        return;
+    }
+
+    let Some(element) = lfml_html5::element(&name) else {
+        push_error(
+            errors,
+            syn::Error::new(tag.span(), format!("unknown html5 element `<{name}>`")),
+        );
+        return;
+    };
+
+    match (element.parents, parent) {
+        (Some(parents), Some(parent)) if !parents.contains(&parent.name) => {
+            let expected = parents
+                .iter()
+                .map(|p| format!("`<{p}>`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            push_error(
+                errors,
+                syn::Error::new(
+                    tag.span(),
+                    format!("`<{name}>` is only allowed directly inside {expected}"),
+                ),
+            );
+        }
+        (Some(_), _) => {}
+        (
+            None,
+            Some(Parent {
+                name: _,
+                element: Some(p),
+            }),
+        ) if !p.allows_child(element) => {
+            push_error(
+                errors,
+                syn::Error::new(
+                    tag.span(),
+                    format!("`<{name}>` is not allowed inside `<{}>`", p.name),
+                ),
+            );
+        }
+        (None, _) => {}
+    }
+
+    for attr in attrs {
+        let attr_name = match attr {
+            TagAttribute::Lit { name, value: _ } => name,
+            TagAttribute::Interpolated {
+                r#type: InterpValue::Toggle { name } | InterpValue::NameValue { name, wrapper: _ },
+                value: _,
+            } => name,
+            TagAttribute::Block { name, value: _ } => name,
+            TagAttribute::Interpolated {
+                r#type: InterpValue::Spread { .. },
+                value: _,
+            }
+            | TagAttribute::Classes(_)
+            | TagAttribute::Conflicts(_)
+            | TagAttribute::Style(_) => continue,
+        };
+
+        if !element.allows_attribute(&attr_name.to_string()) {
+            push_error(
+                errors,
+                syn::Error::new(
+                    attr_name.span(),
+                    format!("attribute `{attr_name}` is not allowed on `<{name}>`"),
+                ),
+            );
+        }
+    }
+
+    let Some(inner) = inner else {
+        return;
+    };
+
+    let constraining = match element.content {
+        ContentModel::Foreign => return,
+        ContentModel::Transparent => parent.and_then(|p| p.element),
+        _ => Some(element),
+    };
+
+    validate_children(
+        Some(Parent {
+            name: &name,
+            element: constraining,
+        }),
+        inner,
+        errors,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::html::parse::LfmlParser;
+
+    use quote::quote;
+
+    fn validate(input: proc_macro2::TokenStream) -> Result<(), String> {
+        let ast = LfmlParser(input.into_iter())
+            .collect::<syn::Result<Vec<_>>>()
+            .unwrap();
+        validate_markup(&ast).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn valid_markup() {
+        assert_eq!(
+            validate(quote! {
+                html lang="en" {
+                    head { title { "x" } meta charset="utf-8"; }
+                    body {
+                        p { "a" span.foo { "b" } a href="/" { "c" } }
+                        ul { @for i in 0..3 { li { (i) } } }
+                        div { a { div { "d" } } }
+                        my-element foo="bar" { "e" }
+                        svg viewBox="0 0 1 1" { path d="M0"; }
+                        button type="submit" data-x="1" onclick="f()" { "f" }
+                    }
+                }
+            }),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn root_fragments_are_valid() {
+        assert_eq!(validate(quote! { li { "a" } td { "b" } }), Ok(()));
+    }
+
+    #[test]
+    fn unknown_tag() {
+        assert_eq!(
+            validate(quote! { div { foo { "x" } } }),
+            Err("unknown html5 element `<foo>`".into())
+        );
+    }
+
+    #[test]
+    fn unknown_attribute() {
+        assert_eq!(
+            validate(quote! { div href="/" {} }),
+            Err("attribute `href` is not allowed on `<div>`".into())
+        );
+    }
+
+    #[test]
+    fn illegal_nesting() {
+        assert_eq!(
+            validate(quote! { p { div {} } }),
+            Err("`<div>` is not allowed inside `<p>`".into())
+        );
+
+        assert_eq!(
+            validate(quote! { p { a { div {} } } }),
+            Err("`<div>` is not allowed inside `<p>`".into())
+        );
+
+        assert_eq!(
+            validate(quote! { div { li {} } }),
+            Err("`<li>` is only allowed directly inside `<ul>`, `<ol>`, `<menu>`".into())
+        );
+
+        assert_eq!(
+            validate(quote! { ul { @if true { p {} } } }),
+            Err("`<p>` is not allowed inside `<ul>`".into())
+        );
+    }
+}
@@ -1,9 +1,10 @@
 extern crate proc_macro;
 
+mod component;
 mod html;
 mod spread;
 
-#[proc_macro_derive(Spread, attributes(prefix, suffix, rename, escape_value, tags))]
+#[proc_macro_derive(Spread, attributes(prefix, suffix, rename, escape_value, tags, spread))]
 pub fn spread(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -18,3 +19,22 @@ pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
+
+#[proc_macro]
+pub fn html_to(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    html::generate_write_to_expr(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn component(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::Item);
+
+    component::generate_component(args.into(), input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -1,10 +1,12 @@
-use crate::spread::syntax::{ImplTags, SpreadBlock, SpreadData, SpreadField, SpreadInput};
+use crate::spread::syntax::{
+    FieldStyle, ImplTags, RenameRule, SpreadBlock, SpreadData, SpreadField, SpreadInput,
+};
 
 use lfml_html5::VALID_HTML5_TAGS;
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
-use syn::{GenericParam, LitStr, TypeParam};
+use syn::{ext::IdentExt, GenericParam, Generics, LitStr, Type, WherePredicate};
 
 pub fn generate_spread_impl(
     SpreadInput {
@@ -12,31 +14,18 @@ pub fn generate_spread_impl(
         data_id,
         fields,
         generics,
+        bound,
     }: SpreadInput,
     output: &mut proc_macro2::TokenStream,
 ) -> syn::Result<()> {
+    let mut generics = generics;
+    let predicates = match bound {
+        Some(bound) => bound,
+        None => infer_bounds(&generics, &fields),
+    };
+    generics.make_where_clause().predicates.extend(predicates);
     let (impl_generics, impl_ty, impl_where) = generics.split_for_impl();
 
-    let disp_where: Vec<proc_macro2::TokenStream> = generics
-        .params
-        .iter()
-        .filter_map(|gp| {
-            if let GenericParam::Type(TypeParam {
-                attrs: _,
-                ident,
-                colon_token: _,
-                bounds: _,
-                eq_token: _,
-                default: _,
-            }) = gp
-            {
-                Some(quote! { where #ident: ::core::fmt::Display })
-            } else {
-                None
-            }
-        })
-        .collect();
-
     let tag_wrapper = format_ident!("{data_id}Tags");
     let tags: Vec<TokenStream> = match tags {
         ImplTags::DefaultWith { include, exclude } => VALID_HTML5_TAGS
@@ -57,8 +46,8 @@ pub fn generate_spread_impl(
             .chain(include.clone().unwrap_or(vec![]))
             .map(|tag| {
                 quote! {
-                    pub fn #tag(&self) -> String {
-                        lfml::Spread::raw(&self.0)
+                    pub fn #tag(&self, attrs: &mut dyn lfml::AttributeSink) {
+                        lfml::Spread::spread_to(self.0, attrs)
                     }
                 }
             })
@@ -67,21 +56,22 @@ pub fn generate_spread_impl(
             .into_iter()
             .map(|tag| {
                 quote! {
-                    pub fn #tag(&self) -> String {
-                        lfml::Spread::raw(&self.0)
+                    pub fn #tag(&self, attrs: &mut dyn lfml::AttributeSink) {
+                        lfml::Spread::spread_to(self.0, attrs)
                     }
                 }
             })
             .collect(),
     };
 
-    let impl_raw_body = match fields {
-        SpreadData::Struct(block) => block.generate_tokens(None),
+    let buf = Ident::new("__lfml_attrs", Span::mixed_site());
+    let impl_body = match fields {
+        SpreadData::Struct(block) => block.generate_tokens(None, &buf),
         SpreadData::Enum(var_blocks) => {
             let mut vars = vec![];
 
             for (var_name, block) in var_blocks {
-                vars.push(block.generate_tokens(Some(var_name)));
+                vars.push(block.generate_tokens(Some(var_name), &buf));
             }
             quote! {
                 match self {
@@ -93,19 +83,19 @@ pub fn generate_spread_impl(
 
     quote! {
         #[automatically_derived]
-        impl #impl_generics lfml::Spread for #data_id #impl_ty #impl_where #(#disp_where),* {
-            fn raw(&self) -> String {
-                #impl_raw_body
+        impl #impl_generics lfml::Spread for #data_id #impl_ty #impl_where {
+            fn spread_to(&self, #buf: &mut dyn lfml::AttributeSink) {
+                #impl_body
             }
         }
 
         pub struct #tag_wrapper<T>(pub T);
 
-        impl #impl_generics #tag_wrapper< &#data_id #impl_ty > #impl_where #(#disp_where),* {
+        impl #impl_generics #tag_wrapper< &#data_id #impl_ty > #impl_where {
             #(#tags)*
         }
 
-        impl #impl_generics #data_id #impl_ty #impl_where #(#disp_where),* {
+        impl #impl_generics #data_id #impl_ty #impl_where {
             pub fn __lfml_tags(&self) -> #tag_wrapper< &#data_id #impl_ty > {
                 #tag_wrapper (self)
             }
@@ -116,11 +106,86 @@ pub fn generate_spread_impl(
     Ok(())
 }
 
+/// The bounds the fields put on the type parameters: `Display` for the type of a value which
+/// mentions one, `lfml::Spread` for a flattened one, and none for the fields which are skipped or
+/// have no value, or whose value is formatted by a `with` function.
+fn infer_bounds(generics: &Generics, fields: &SpreadData) -> Vec<WherePredicate> {
+    let params: Vec<&Ident> = generics
+        .params
+        .iter()
+        .filter_map(|gp| match gp {
+            GenericParam::Type(t) => Some(&t.ident),
+            _ => None,
+        })
+        .collect();
+    if params.is_empty() {
+        return vec![];
+    }
+
+    fn mentions(tokens: TokenStream, params: &[&Ident]) -> bool {
+        tokens.into_iter().any(|t| match t {
+            proc_macro2::TokenTree::Ident(i) => params.iter().any(|p| **p == i),
+            proc_macro2::TokenTree::Group(g) => mentions(g.stream(), params),
+            _ => false,
+        })
+    }
+
+    let blocks: Vec<&SpreadBlock> = match fields {
+        SpreadData::Struct(block) => vec![block],
+        SpreadData::Enum(vars) => vars.iter().map(|(_, block)| block).collect(),
+    };
+
+    let mut predicates: Vec<WherePredicate> = vec![];
+    for field in blocks.into_iter().flat_map(|b| &b.fields) {
+        let field_predicates = match &field.bound {
+            Some(bound) => bound.clone(),
+            None if field.is_skipped
+                || field.is_bool
+                || field.is_name_only
+                || field.with.is_some()
+                || !mentions(field.value_ty.to_token_stream(), &params) =>
+            {
+                vec![]
+            }
+            None => {
+                let ty: &Type = &field.value_ty;
+                vec![if field.is_flatten {
+                    syn::parse_quote! { #ty: lfml::Spread }
+                } else {
+                    syn::parse_quote! { #ty: ::core::fmt::Display }
+                }]
+            }
+        };
+        for predicate in field_predicates {
+            if !predicates.contains(&predicate) {
+                predicates.push(predicate);
+            }
+        }
+    }
+    predicates
+}
+
 impl SpreadBlock {
-    fn generate_tokens(&self, var_name: Option<Ident>) -> TokenStream {
+    /// The statements adding the attributes to the `&mut dyn lfml::AttributeSink` `buf`, or the
+    /// match arm doing so for the variant `var_name`.
+    fn generate_tokens(&self, var_name: Option<Ident>, buf: &Ident) -> TokenStream {
         let mut fs = TokenStream::new();
-        let mut fmt_value_exprs = vec![];
-        let mut fmt_string = String::new();
+        let mut stmts = vec![];
+
+        for (name, value) in &self.attrs {
+            let value = value.as_ref().map(|v| {
+                let v = fmt_literal(&lfml_escape::attr::escape_string(v));
+                quote! { ::core::format_args!(#v) }
+            });
+            let value = match value {
+                Some(v) => quote! { Some(#v) },
+                None => quote! { None },
+            };
+            stmts.push(quote! { #buf.attribute(#name, #value); });
+        }
+
+        // tuple fields are bound by destructuring `self`, named fields are accessed through it.
+        let bound = var_name.is_some() || self.style == FieldStyle::Unnamed;
 
         for SpreadField {
             rename,
@@ -128,16 +193,54 @@ impl SpreadBlock {
             is_option,
             is_escaped,
             is_name_only,
+            is_flatten,
+            is_skipped,
+            is_bool,
+            skip_if,
+            with,
+            default,
+            ..
         } in self.fields.clone().into_iter()
         {
+            if is_skipped {
+                quote! { #name: _, }.to_tokens(&mut fs);
+                continue;
+            }
+            quote! { #name, }.to_tokens(&mut fs);
+
+            let field_ref = if bound {
+                quote! { #name }
+            } else {
+                quote! { &self.#name }
+            };
+
+            if is_flatten {
+                let write = if is_option {
+                    quote! {
+                        if let Some(x) = #field_ref {
+                            lfml::Spread::spread_to(x, #buf);
+                        }
+                    }
+                } else {
+                    quote! { lfml::Spread::spread_to(#field_ref, #buf); }
+                };
+                stmts.push(skip_field(skip_if.as_ref(), &field_ref, write));
+                continue;
+            }
+
             let attribute_name = if let Some(t) = rename {
                 t.to_string()
             } else {
+                let field_name = name.unraw().to_string();
+                let field_name = match self.rename_all {
+                    Some(rule) => rule.apply(&field_name),
+                    None => field_name,
+                };
                 let t = if let Some(pfx) = self.prefix.as_ref() {
                     // TODO: a guard on the length of p > 0? maybe that lives upstairs
-                    format!("{pfx}-{name}")
+                    format!("{pfx}-{field_name}")
                 } else {
-                    name.to_string()
+                    field_name
                 };
 
                 if let Some(sfx) = self.suffix.as_ref() {
@@ -148,71 +251,123 @@ impl SpreadBlock {
                 }
             };
 
-            let fmt_attr = if !is_name_only {
-                format!(" {}=\"{{}}\"", attribute_name)
-            } else {
-                format!(" {}", attribute_name)
-            };
-
-            let field_ident = if var_name.is_some() {
-                quote! { &#name }
-            } else {
-                quote! { &self.#name }
-            };
-
-            let escape_value = |val| {
-                if is_escaped {
-                    quote! { lfml::escape_string( &#val.to_string() ) }
+            let write_value = |v: TokenStream| {
+                let v = match &with {
+                    Some(with) => quote! { #with(#v) },
+                    None => v,
+                };
+                let v = if is_escaped {
+                    quote! { lfml::escape::Escape(#v) }
                 } else {
-                    quote! { #val }
+                    v
+                };
+                quote! {
+                    #buf.attribute(#attribute_name, Some(::core::format_args!("{}", #v)));
                 }
             };
 
-            let fmt_expr = if is_option {
-                let fmt_attr = LitStr::new(&fmt_attr, name.span());
-
-                let fmt_value = escape_value(quote! { x });
-
-                if !is_name_only {
-                    Some(quote! { &{
-                        if let Some(ref x) = #field_ident {
-                            format!(#fmt_attr, #fmt_value)
-                        } else {
-                            "".into()
-                        }
-                    }})
-                } else {
-                    Some(quote! {
-                        if let Some(_) = #field_ident {
-                            #fmt_attr
-                        } else {
-                            ""
+            let write = if is_bool {
+                quote! {
+                    if *#field_ref {
+                        #buf.attribute(#attribute_name, None);
+                    }
+                }
+            } else if is_option && is_name_only {
+                quote! {
+                    if (#field_ref).is_some() {
+                        #buf.attribute(#attribute_name, None);
+                    }
+                }
+            } else if is_option {
+                let some = write_value(quote! { x });
+                let none = default.map(|default| {
+                    let default = fmt_literal(&lfml_escape::attr::escape_string(&default));
+                    quote! {
+                        else {
+                            #buf.attribute(#attribute_name, Some(::core::format_args!(#default)));
                         }
-                    })
+                    }
+                });
+                quote! {
+                    if let Some(x) = #field_ref {
+                        #some
+                    } #none
                 }
-            } else if !is_name_only {
-                Some(escape_value(quote! { &{ #field_ident } }))
+            } else if is_name_only {
+                quote! { #buf.attribute(#attribute_name, None); }
             } else {
-                None
+                write_value(quote! { #field_ref })
             };
 
-            if let Some(fmt_expr) = fmt_expr {
-                fmt_value_exprs.push(fmt_expr);
-            }
-            fmt_string.push_str(if is_option { "{}" } else { &fmt_attr });
-            quote! { #name, }.to_tokens(&mut fs);
+            stmts.push(skip_field(skip_if.as_ref(), &field_ref, write));
         }
-        let fmt_lit = LitStr::new(&fmt_string, Span::mixed_site());
+
+        let pattern = match self.style {
+            FieldStyle::Named => quote! { { #fs } },
+            FieldStyle::Unnamed => quote! { ( #fs ) },
+            FieldStyle::Unit => TokenStream::new(),
+        };
 
         match var_name {
             Some(var) => quote! {
-                Self::#var { #fs } => {
-                    format!(#fmt_lit, #(#fmt_value_exprs),*)
+                Self::#var #pattern => {
+                    #(#stmts)*
                 }
             },
-            None => quote! {
-                format!(#fmt_lit, #(#fmt_value_exprs),*)
+            None if self.style == FieldStyle::Unnamed => quote! {
+                let Self #pattern = self;
+                #(#stmts)*
             },
+            None => quote! { #(#stmts)* },
+        }
+    }
+}
+
+impl RenameRule {
+    /// Renames the snake case `field`.
+    fn apply(self, field: &str) -> String {
+        let capitalize = |w: &str| {
+            let mut c = w.chars();
+            c.next()
+                .map(|f| f.to_uppercase().chain(c).collect::<String>())
+                .unwrap_or_default()
+        };
+        let words = field.split('_');
+
+        match self {
+            // like serde, which leaves the underscores of fields in these two.
+            RenameRule::Lower => field.to_string(),
+            RenameRule::Upper => field.to_ascii_uppercase(),
+            RenameRule::Pascal => words.map(capitalize).collect(),
+            RenameRule::Camel => words
+                .enumerate()
+                .map(|(i, w)| if i == 0 { w.to_string() } else { capitalize(w) })
+                .collect(),
+            RenameRule::Snake => field.to_string(),
+            RenameRule::ScreamingSnake => field.to_uppercase(),
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.replace('_', "-").to_uppercase(),
         }
     }
 }
+
+/// `s` as a format string which formats as `s`.
+fn fmt_literal(s: &str) -> LitStr {
+    LitStr::new(&s.replace('{', "{{").replace('}', "}}"), Span::mixed_site())
+}
+
+/// Puts the statements writing a field under a condition with `#[spread(skip_if = "...")]`.
+fn skip_field(
+    skip_if: Option<&syn::Path>,
+    field_ref: &TokenStream,
+    write: TokenStream,
+) -> TokenStream {
+    match skip_if {
+        Some(skip_if) => quote! {
+            if !#skip_if(#field_ref) {
+                #write
+            }
+        },
+        None => write,
+    }
+}
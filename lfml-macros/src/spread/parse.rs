@@ -1,12 +1,14 @@
 use proc_macro2::{Delimiter, Ident, TokenTree};
+use quote::format_ident;
 use syn::{
-    spanned::Spanned, AngleBracketedGenericArguments, AttrStyle, Attribute, Data, DataEnum,
-    DataStruct, DeriveInput, Expr, ExprLit, Field, Fields, FieldsNamed, GenericArgument, Lit, Meta,
-    MetaList, MetaNameValue, PathArguments, Type, TypePath, Variant,
+    ext::IdentExt, parenthesized, parse::ParseStream, punctuated::Punctuated, spanned::Spanned,
+    AngleBracketedGenericArguments, AttrStyle, Attribute, Data, DataEnum, DataStruct, DeriveInput,
+    Expr, ExprLit, Field, Fields, FieldsNamed, FieldsUnnamed, GenericArgument, Lit, LitStr, Meta,
+    MetaList, MetaNameValue, PathArguments, Token, Type, TypePath, Variant, WherePredicate,
 };
 
 use crate::spread::{
-    syntax::{ImplTags, SpreadBlock, SpreadData, SpreadField, SpreadInput},
+    syntax::{FieldStyle, ImplTags, RenameRule, SpreadBlock, SpreadData, SpreadField, SpreadInput},
     DATA_PREFIX,
 };
 
@@ -21,7 +23,12 @@ impl SpreadField {
             ty,
         }: Field,
     ) -> syn::Result<Self> {
-        let (is_option, is_name_only) = match ty {
+        let is_phantom = matches!(
+            &ty,
+            Type::Path(TypePath { qself: _, path })
+                if path.segments.last().is_some_and(|ps| ps.ident == "PhantomData")
+        );
+        let (is_option, is_name_only) = match &ty {
             Type::Path(TypePath { qself: _, path })
                 if path
                     .segments
@@ -73,7 +80,14 @@ impl SpreadField {
         };
 
         let mut is_escaped = false;
+        let mut is_flatten = false;
+        let mut is_skipped = is_phantom;
+        let mut is_bool = false;
+        let mut skip_if = None;
+        let mut with = None;
+        let mut default = None;
         let mut rename = None;
+        let mut bound = None;
         for Attribute {
             pound_token: _,
             style,
@@ -105,20 +119,234 @@ impl SpreadField {
                     };
                     rename.replace(s.value());
                 }
+                (AttrStyle::Outer, Meta::List(l)) if l.path.is_ident("spread") => {
+                    l.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("flatten") {
+                            is_flatten = true;
+                        } else if meta.path.is_ident("skip") {
+                            is_skipped = true;
+                        } else if meta.path.is_ident("bool") {
+                            is_bool = true;
+                        } else if meta.path.is_ident("skip_if") {
+                            skip_if = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                        } else if meta.path.is_ident("with") {
+                            with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                        } else if meta.path.is_ident("default") {
+                            default = Some(meta.value()?.parse::<LitStr>()?.value());
+                        } else if meta.path.is_ident("bound") {
+                            bound = Some(parse_bound(&meta.value()?.parse()?)?);
+                        } else {
+                            return Err(meta.error(
+                                "expected `flatten`, `skip`, `skip_if = \"...\"`, `with = \"...\"`, `bool`, `default = \"...\"` or `bound = \"...\"`",
+                            ));
+                        }
+                        Ok(())
+                    })?;
+                }
                 (_, _) => return Err(syn::Error::new(span, "unexpected attribute")),
             }
         }
 
+        let name = ident.expect("named field");
+        let conflict = if is_bool && (is_option || is_name_only) {
+            Some("`#[spread(bool)]` is for `bool` fields, not `Option`s or `NameOnly`")
+        } else if default.is_some() && (!is_option || is_name_only) {
+            Some("`#[spread(default = \"...\")]` is for `Option` fields with a value")
+        } else if is_flatten && (is_bool || is_escaped || with.is_some() || default.is_some()) {
+            Some("a `#[spread(flatten)]` field is written by its own `Spread` impl")
+        } else if (is_bool || is_name_only) && with.is_some() {
+            Some("name-only attributes have no value to format")
+        } else {
+            None
+        };
+        if let Some(conflict) = conflict {
+            return Err(syn::Error::new(name.span(), conflict));
+        }
+
+        let value_ty = match &ty {
+            Type::Path(TypePath { qself: _, path }) if is_option => {
+                match path.segments.last().map(|ps| &ps.arguments) {
+                    Some(PathArguments::AngleBracketed(a)) => match a.args.last() {
+                        Some(GenericArgument::Type(t)) => t.clone(),
+                        _ => ty.clone(),
+                    },
+                    _ => ty.clone(),
+                }
+            }
+            _ => ty.clone(),
+        };
+
         Ok(SpreadField {
             rename,
-            name: ident.expect("named field"),
+            name,
             is_option,
             is_escaped,
             is_name_only,
+            is_flatten,
+            is_skipped,
+            is_bool,
+            skip_if,
+            with,
+            default,
+            value_ty,
+            bound,
+        })
+    }
+
+    /// Parses the field of a tuple struct or variant, which is spread with its `Spread` impl.
+    fn parse_unnamed(index: usize, field: Field) -> syn::Result<Self> {
+        if let Some(attr) = field.attrs.first() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "the fields of tuple structs and variants are spread as a whole, and take no attributes",
+            ));
+        }
+
+        Ok(SpreadField {
+            is_flatten: true,
+            ..Self::parse(Field {
+                ident: Some(format_ident!("__lfml_{index}")),
+                ..field
+            })?
+        })
+    }
+}
+
+impl SpreadBlock {
+    fn parse(
+        prefix: Option<String>,
+        suffix: Option<String>,
+        attrs: Vec<(String, Option<String>)>,
+        rename_all: Option<RenameRule>,
+        fields: Fields,
+    ) -> syn::Result<Self> {
+        let (style, fields) = match fields {
+            Fields::Named(FieldsNamed { named, .. }) => (
+                FieldStyle::Named,
+                named
+                    .into_iter()
+                    .map(SpreadField::parse)
+                    .collect::<syn::Result<Vec<_>>>()?,
+            ),
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => (
+                FieldStyle::Unnamed,
+                unnamed
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, f)| SpreadField::parse_unnamed(i, f))
+                    .collect::<syn::Result<Vec<_>>>()?,
+            ),
+            Fields::Unit => (FieldStyle::Unit, vec![]),
+        };
+
+        Ok(SpreadBlock {
+            prefix,
+            suffix,
+            attrs,
+            rename_all,
+            style,
+            fields,
+        })
+    }
+}
+
+/// Parses the `#[spread(...)]` of a struct, enum or variant: `attrs(#name = #value, #name, ...)`,
+/// its fixed attributes, `rename_all = "..."`, and on the struct or enum, `bound = "..."`.
+fn parse_block_options(
+    list: &MetaList,
+    attrs: &mut Vec<(String, Option<String>)>,
+    rename_all: &mut Option<RenameRule>,
+    mut bound: Option<&mut Option<Vec<WherePredicate>>>,
+) -> syn::Result<()> {
+    list.parse_nested_meta(|meta| {
+        if meta.path.is_ident("rename_all") {
+            let rule = meta.value()?.parse::<LitStr>()?;
+            *rename_all = Some(RenameRule::parse(&rule)?);
+            return Ok(());
+        }
+        if let (true, Some(bound)) = (meta.path.is_ident("bound"), bound.as_deref_mut()) {
+            *bound = Some(parse_bound(&meta.value()?.parse()?)?);
+            return Ok(());
+        }
+        if !meta.path.is_ident("attrs") {
+            return Err(meta.error("expected `attrs(...)` or `rename_all = \"...\"`"));
+        }
+
+        let content;
+        parenthesized!(content in meta.input);
+        while !content.is_empty() {
+            let name = parse_attr_name(&content)?;
+            let value = if content.peek(Token![=]) {
+                content.parse::<Token![=]>()?;
+                Some(match content.parse::<Lit>()? {
+                    Lit::Str(s) => s.value(),
+                    Lit::Int(i) => i.base10_digits().to_string(),
+                    Lit::Bool(b) => b.value.to_string(),
+                    l => {
+                        return Err(syn::Error::new(
+                            l.span(),
+                            "expected a string, integer or bool value",
+                        ))
+                    }
+                })
+            } else {
+                None
+            };
+            attrs.push((name, value));
+
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+        Ok(())
+    })
+}
+
+impl RenameRule {
+    fn parse(rule: &LitStr) -> syn::Result<Self> {
+        Ok(match rule.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => {
+                return Err(syn::Error::new(
+                    rule.span(),
+                    "expected one of \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\" or \"SCREAMING-KEBAB-CASE\"",
+                ))
+            }
         })
     }
 }
 
+/// Parses the where predicates of `#[spread(bound = "...")]`, which may be empty.
+fn parse_bound(bound: &LitStr) -> syn::Result<Vec<WherePredicate>> {
+    Ok(bound
+        .parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?
+        .into_iter()
+        .collect())
+}
+
+/// An attribute name, either a string literal or hyphenated identifiers, e.g. `aria-label`.
+fn parse_attr_name(input: ParseStream) -> syn::Result<String> {
+    if input.peek(LitStr) {
+        return Ok(input.parse::<LitStr>()?.value());
+    }
+
+    let mut name = Ident::parse_any(input)?.unraw().to_string();
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        name.push('-');
+        name.push_str(&Ident::parse_any(input)?.unraw().to_string());
+    }
+    Ok(name)
+}
+
 impl SpreadInput {
     pub fn parse(
         DeriveInput {
@@ -135,6 +363,9 @@ impl SpreadInput {
         };
         let mut prefix: Option<String> = None;
         let mut suffix: Option<String> = None;
+        let mut fixed_attrs = vec![];
+        let mut rename_all = None;
+        let mut bound = None;
 
         for Attribute {
             pound_token: _,
@@ -259,27 +490,27 @@ impl SpreadInput {
                         }
                     }
                 }
+                (AttrStyle::Outer, syn::Meta::List(l)) if l.path.is_ident("spread") => {
+                    parse_block_options(l, &mut fixed_attrs, &mut rename_all, Some(&mut bound))?;
+                    if let (Data::Enum(_), false) = (&data, fixed_attrs.is_empty()) {
+                        return Err(syn::Error::new(
+                            l.span(),
+                            "`#[spread(attrs(...))]` goes on the variants of an enum",
+                        ));
+                    }
+                }
                 _ => return Err(syn::Error::new(meta.span(), "unexpected attribute")),
             }
         }
 
         let fields = match data {
-            Data::Struct(DataStruct {
-                struct_token: _,
-                fields:
-                    Fields::Named(FieldsNamed {
-                        brace_token: _,
-                        named: fields,
-                    }),
-                semi_token: _,
-            }) => SpreadData::Struct(SpreadBlock {
+            Data::Struct(DataStruct { fields, .. }) => SpreadData::Struct(SpreadBlock::parse(
                 prefix,
                 suffix,
-                fields: fields
-                    .into_iter()
-                    .map(SpreadField::parse)
-                    .collect::<syn::Result<Vec<_>>>()?,
-            }),
+                fixed_attrs,
+                rename_all,
+                fields,
+            )?),
             Data::Enum(DataEnum {
                 enum_token: _,
                 brace_token: _,
@@ -294,6 +525,8 @@ impl SpreadInput {
                     discriminant: _,
                 } in variants.into_iter()
                 {
+                    let mut fixed_attrs = vec![];
+                    let mut rename_all = rename_all;
                     for Attribute {
                         pound_token: _,
                         style,
@@ -347,36 +580,32 @@ impl SpreadInput {
                                 };
                                 suffix.replace(s.value());
                             }
+                            (AttrStyle::Outer, syn::Meta::List(l)) if l.path.is_ident("spread") => {
+                                parse_block_options(l, &mut fixed_attrs, &mut rename_all, None)?;
+                            }
                             _ => return Err(syn::Error::new(meta.span(), "unexpected attribute")),
                         }
                     }
-                    let Fields::Named(FieldsNamed {
-                        brace_token: _,
-                        named: fields,
-                    }) = fields
-                    else {
-                        return Err(syn::Error::new(
-                            var_ident.span(),
-                            "Only variants with named fields are supported",
-                        ));
-                    };
 
                     vars.push((
                         var_ident.clone(),
-                        SpreadBlock {
-                            prefix: prefix.clone(),
-                            suffix: suffix.clone(),
-                            fields: fields
-                                .into_iter()
-                                .map(SpreadField::parse)
-                                .collect::<syn::Result<Vec<_>>>()?,
-                        },
+                        SpreadBlock::parse(
+                            prefix.clone(),
+                            suffix.clone(),
+                            fixed_attrs,
+                            rename_all,
+                            fields,
+                        )?,
                     ));
                 }
                 SpreadData::Enum(vars)
             }
-            Data::Union(_) => todo!("unions can be implemented"),
-            _ => todo!("unimplemented combination"),
+            Data::Union(u) => {
+                return Err(syn::Error::new(
+                    u.union_token.span,
+                    "Spread can't be derived for unions",
+                ))
+            }
         };
 
         Ok(SpreadInput {
@@ -384,6 +613,7 @@ impl SpreadInput {
             data_id: struct_ident,
             fields,
             generics,
+            bound,
         })
     }
 }
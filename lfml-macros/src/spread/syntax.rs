@@ -1,5 +1,5 @@
 use proc_macro2::Ident;
-use syn::Generics;
+use syn::{Generics, Type, WherePredicate};
 
 #[derive(Debug, Clone)]
 pub struct SpreadInput {
@@ -9,6 +9,9 @@ pub struct SpreadInput {
     pub data_id: Ident,
     /// Generics data, required for the output TokenStream.
     pub generics: Generics,
+    /// `#[spread(bound = "...")]`: the where predicates of the impl, instead of those inferred
+    /// from the fields.
+    pub bound: Option<Vec<WherePredicate>>,
     /// Field names for writing out the Spread implementation.
     pub fields: SpreadData,
 }
@@ -103,10 +106,54 @@ pub struct SpreadBlock {
     /// ```
     /// the acceptable form is #[suffix = "foo"]
     pub suffix: Option<String>,
+    /// Fixed attributes, written before the fields:
+    /// ```ignore
+    /// #[derive(Spread)]
+    /// enum Button {
+    ///     #[spread(attrs(r#type = "submit", aria-label = "Send"))]
+    ///     Submit,
+    /// }
+    /// ```
+    pub attrs: Vec<(String, Option<String>)>,
+    /// `#[spread(rename_all = "...")]` on the struct, or on the enum or the variant, which applies
+    /// to the fields without a `#[rename]`.
+    pub rename_all: Option<RenameRule>,
+    /// Whether the fields are named, unnamed (in which case each of them is a `Spread` itself), or
+    /// absent.
+    pub style: FieldStyle,
     /// Field names and flaggs.
     pub fields: Vec<SpreadField>,
 }
 
+/// The case conventions of `#[spread(rename_all = "...")]`, named as in serde, which turn the snake
+/// case name of a field into the name of its attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`, which leaves field names as they are, underscores included, like serde
+    Lower,
+    /// `UPPERCASE`, which keeps the underscores of field names, like serde
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldStyle {
+    Named,
+    Unnamed,
+    Unit,
+}
+
 #[derive(Debug, Clone)]
 pub struct SpreadField {
     pub rename: Option<String>,
@@ -114,4 +161,23 @@ pub struct SpreadField {
     pub is_option: bool,
     pub is_escaped: bool,
     pub is_name_only: bool,
+    /// `#[spread(flatten)]`, or a tuple field: the field is a `Spread` whose attributes are written
+    /// in its place.
+    pub is_flatten: bool,
+    /// `#[spread(skip)]`, or a `PhantomData`: the field isn't written.
+    pub is_skipped: bool,
+    /// `#[spread(bool)]`: the `bool` field is written as a name-only attribute when `true`.
+    pub is_bool: bool,
+    /// `#[spread(skip_if = "path")]`: the field isn't written when `path(&field)` is `true`.
+    pub skip_if: Option<syn::Path>,
+    /// `#[spread(with = "path")]`: the value written is `path(&field)`, or `path(&x)` for an
+    /// `Option` which is `Some(x)`.
+    pub with: Option<syn::Path>,
+    /// `#[spread(default = "value")]`: what an `Option` field is written as when it's `None`.
+    pub default: Option<String>,
+    /// The type of the value written, i.e. `T` for an `Option<T>` field.
+    pub value_ty: Type,
+    /// `#[spread(bound = "...")]`: the where predicates the field needs, instead of e.g.
+    /// `T: Display` for a field of type `T`.
+    pub bound: Option<Vec<WherePredicate>>,
 }
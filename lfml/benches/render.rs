@@ -0,0 +1,317 @@
+//! Compares the code `html!` generates against what it generated before literals were merged
+//! across tags and interpolated attributes were escaped straight into the output.
+//!
+//! Run with `cargo bench -p lfml`.
+//!
+//! The `before` functions are the expansions of the same templates as the previous generator wrote
+//! them: a push per literal and per closing tag, and a `format!` for every opening tag with
+//! interpolated attributes.
+//!
+//! Merging the literals of a static page only saves a few pushes, so it's a little faster; a table
+//! whose rows have interpolated attributes renders several times faster without the `format!`s.
+//!
+//! The spread table compares spreads written straight into the output, or into the shared buffer
+//! of `lfml::Attributes` when they're merged with other attributes, against the `String` per name
+//! and value they took before.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use lfml::{escape, html, Markup, Render, Spread};
+
+struct Row {
+    id: u32,
+    name: String,
+    url: String,
+    title: Option<String>,
+    selected: bool,
+}
+
+fn static_page() -> Markup {
+    html! {
+        header .site-header {
+            nav {
+                ul .menu {
+                    li { a href="/" { "Home" } }
+                    li { a href="/blog" { "Blog" } }
+                    li { a href="/about" { "About" } }
+                }
+            }
+        }
+        main {
+            h1 { "Welcome" }
+            p { "A " em { "static" } " page, made of " strong { "literals" } " only." }
+        }
+        footer { p { "© 2023" } }
+    }
+}
+
+fn static_page_before() -> String {
+    let mut out = String::with_capacity(512);
+    out.push_str("<header class=\"site-header\">");
+    {
+        out.push_str("<nav>");
+        {
+            out.push_str("<ul class=\"menu\">");
+            {
+                out.push_str("<li>");
+                {
+                    out.push_str("<a href=\"/\">");
+                    out.push_str("Home");
+                    out.push_str("</a>");
+                }
+                out.push_str("</li>");
+                out.push_str("<li>");
+                {
+                    out.push_str("<a href=\"/blog\">");
+                    out.push_str("Blog");
+                    out.push_str("</a>");
+                }
+                out.push_str("</li>");
+                out.push_str("<li>");
+                {
+                    out.push_str("<a href=\"/about\">");
+                    out.push_str("About");
+                    out.push_str("</a>");
+                }
+                out.push_str("</li>");
+            }
+            out.push_str("</ul>");
+        }
+        out.push_str("</nav>");
+    }
+    out.push_str("</header>");
+    out.push_str("<main>");
+    {
+        out.push_str("<h1>");
+        out.push_str("Welcome");
+        out.push_str("</h1>");
+        out.push_str("<p>");
+        {
+            out.push_str("A ");
+            out.push_str("<em>");
+            out.push_str("static");
+            out.push_str("</em>");
+            out.push_str(" page, made of ");
+            out.push_str("<strong>");
+            out.push_str("literals");
+            out.push_str("</strong>");
+            out.push_str(" only.");
+        }
+        out.push_str("</p>");
+    }
+    out.push_str("</main>");
+    out.push_str("<footer>");
+    out.push_str("<p>");
+    out.push_str("© 2023");
+    out.push_str("</p>");
+    out.push_str("</footer>");
+    out
+}
+
+fn table(rows: &[Row]) -> Markup {
+    html! {
+        table .rows {
+            @for row in rows {
+                tr id=(row.id) hidden[row.selected] {
+                    td { a href=(&row.url) title=[row.title.as_ref()] { (&row.name) } }
+                    td .count { (row.id * 3) }
+                }
+            }
+        }
+    }
+}
+
+fn table_before(rows: &[Row]) -> String {
+    let mut out = String::with_capacity(512);
+    out.push_str("<table class=\"rows\">");
+    {
+        for row in rows {
+            out.push_str(&format!(
+                "<tr id=\"{}\"{}>",
+                escape::attr::escape_string(&row.id.to_string()),
+                if row.selected { " hidden" } else { "" },
+            ));
+            {
+                out.push_str("<td>");
+                {
+                    out.push_str(&format!(
+                        "<a href=\"{}\"{}>",
+                        escape::url::escape_string(&row.url.to_string()),
+                        if let Some(e) = row.title.as_ref() {
+                            format!(" title=\"{}\"", escape::attr::escape_string(&e.to_string()))
+                        } else {
+                            "".into()
+                        },
+                    ));
+                    {
+                        row.name.markup_to_string(&mut out);
+                    }
+                    out.push_str("</a>");
+                }
+                out.push_str("</td>");
+                out.push_str("<td class=\"count\">");
+                {
+                    (row.id * 3).markup_to_string(&mut out);
+                }
+                out.push_str("</td>");
+            }
+            out.push_str("</tr>");
+        }
+    }
+    out.push_str("</table>");
+    out
+}
+
+#[derive(lfml::Spread)]
+struct RowAttrs<'a> {
+    id: u32,
+    selected: bool,
+    title: Option<&'a str>,
+}
+
+#[derive(lfml::Spread)]
+struct LinkAttrs<'a> {
+    class: &'a str,
+    rel: &'a str,
+}
+
+fn spread_table(rows: &[Row]) -> Markup {
+    html! {
+        table .rows {
+            @for row in rows {
+                tr @(RowAttrs { id: row.id, selected: row.selected, title: row.title.as_deref() }) {
+                    td {
+                        a.link href=(&row.url) @(LinkAttrs { class: "external", rel: "noopener" }) {
+                            (&row.name)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The `lfml::Attributes` every tag with a spread collected its attributes in before, which took a
+/// `String` for each name and value the spread added, and for each value of the template.
+mod before {
+    use std::{borrow::Cow, fmt};
+
+    use lfml::{escape::url, AttributeSink};
+
+    #[derive(Default)]
+    pub struct Attributes<'a>(Vec<(Cow<'a, str>, Option<Cow<'a, str>>)>);
+
+    impl<'a> Attributes<'a> {
+        pub fn push(&mut self, name: impl Into<Cow<'a, str>>, value: Option<Cow<'a, str>>) {
+            let name = name.into();
+            let value = match value {
+                Some(v) if url::is_url_attribute(&name) && !url::is_safe_escaped(&v) => {
+                    Some(Cow::Borrowed(url::UNSAFE_URL))
+                }
+                v => v,
+            };
+            let Some(i) = self.0.iter().position(|(n, _)| *n == name) else {
+                self.0.push((name, value));
+                return;
+            };
+            let previous = &mut self.0[i].1;
+            *previous = match (previous.take(), value) {
+                (Some(p), Some(v)) if name == "class" => Some(format!("{p} {v}").into()),
+                (_, v) => v,
+            };
+        }
+
+        pub fn render(&self, out: &mut String) {
+            for (name, value) in &self.0 {
+                match value {
+                    Some(value) => out.push_str(&format!(" {name}=\"{value}\"")),
+                    None => out.push_str(&format!(" {name}")),
+                }
+            }
+        }
+    }
+
+    impl AttributeSink for Attributes<'_> {
+        fn attribute(&mut self, name: &str, value: Option<fmt::Arguments<'_>>) {
+            self.push(name.to_owned(), value.map(|v| Cow::Owned(v.to_string())));
+        }
+    }
+}
+
+fn spread_table_before(rows: &[Row]) -> String {
+    let mut out = String::with_capacity(512);
+    out.push_str("<table class=\"rows\">");
+    for row in rows {
+        out.push_str("<tr");
+        {
+            let mut attrs = before::Attributes::default();
+            let spread = RowAttrs {
+                id: row.id,
+                selected: row.selected,
+                title: row.title.as_deref(),
+            };
+            Spread::spread_to(&spread, &mut attrs);
+            attrs.render(&mut out);
+        }
+        out.push_str("><td>");
+        out.push_str("<a");
+        {
+            let mut attrs = before::Attributes::default();
+            attrs.push("class", Some("link".into()));
+            attrs.push(
+                "href",
+                Some(escape::attr::escape_string(&row.url.to_string()).into()),
+            );
+            let spread = LinkAttrs {
+                class: "external",
+                rel: "noopener",
+            };
+            Spread::spread_to(&spread, &mut attrs);
+            attrs.render(&mut out);
+        }
+        out.push('>');
+        row.name.markup_to_string(&mut out);
+        out.push_str("</a></td></tr>");
+    }
+    out.push_str("</table>");
+    out
+}
+
+fn bench(name: &str, f: impl Fn() -> String) {
+    let mut iterations = 0u32;
+    let mut len = 0;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(500) {
+        len = black_box(f()).len();
+        iterations += 1;
+    }
+    let per_iter = start.elapsed() / iterations;
+    let throughput = len as f64 / per_iter.as_secs_f64() / (1024.0 * 1024.0);
+    println!("{name:<24} {per_iter:>12.2?}/iter {throughput:>10.1} MiB/s");
+}
+
+fn main() {
+    assert_eq!(static_page().into_inner(), static_page_before());
+    bench("static/before", static_page_before);
+    bench("static/html!", || static_page().into_inner());
+
+    let rows = (0..100)
+        .map(|id| Row {
+            id,
+            name: format!("row <{id}>"),
+            url: format!("/rows/{id}?sort=name&dir=asc"),
+            title: (id % 3 == 0).then(|| format!("Row \"{id}\"")),
+            selected: id % 7 == 0,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(table(&rows).into_inner(), table_before(&rows));
+    bench("table/before", || table_before(black_box(&rows)));
+    bench("table/html!", || table(black_box(&rows)).into_inner());
+
+    assert_eq!(spread_table(&rows).into_inner(), spread_table_before(&rows));
+    bench("spread/before", || spread_table_before(black_box(&rows)));
+    bench("spread/html!", || {
+        spread_table(black_box(&rows)).into_inner()
+    });
+}
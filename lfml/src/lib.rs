@@ -1,13 +1,31 @@
 mod integrations;
+mod pretty;
+mod sanitize;
 mod types;
 
+pub use crate::pretty::pretty;
+pub use crate::sanitize::{sanitize, sanitize_with};
+
 pub use crate::types::{
-    attrs::{NameOnly, Spread},
-    markup::{Escaped, Render},
+    attrs::{AttributeSink, AttributeWriter, Attributes, Conflicts, NameOnly, Spread},
+    capacity::Capacity,
+    classes::Classes,
+    io::IoWriter,
+    join::{join, Join},
+    looped::{looped, Loop, Looped},
+    markup::{raw, Escaped, PreEscaped, Render},
+    slot::Slot,
+    style::Style,
 };
 
-pub use lfml_escape::{escape_string, escape_to_string};
-pub use lfml_macros::{html, Spread};
+#[doc(hidden)]
+pub use crate::types::markup::{__AuditRaw, __FromHtml};
+#[doc(hidden)]
+pub use crate::types::slot::{__Component, __call_component};
+
+pub use lfml_escape as escape;
+pub use lfml_escape::{escape_string, escape_to_string, escape_to_writer};
+pub use lfml_macros::{component, html, html_to, Spread};
 
 pub type Markup = Escaped<String>;
 
@@ -0,0 +1,323 @@
+use crate::{Escaped, Markup, Render};
+
+/// Indents rendered markup, putting each block element on its own line, e.g. to get readable
+/// snapshots of whole pages:
+///
+/// ```
+/// let page = lfml::html! {
+///     ul { li { "One" } li { "Two and " em { "a half" } } }
+///     p { "Hello, " a href="/world" { "world" } }
+/// };
+///
+/// assert_eq!(
+///     lfml::pretty(&page).into_inner(),
+///     concat!(
+///         "<ul>\n",
+///         "  <li>One</li>\n",
+///         "  <li>Two and <em>a half</em></li>\n",
+///         "</ul>\n",
+///         "<p>Hello, <a href=\"/world\">world</a></p>",
+///     ),
+/// );
+/// ```
+///
+/// Inline elements, i.e. the phrasing content of html5, stay on the line of the text around them,
+/// unless they contain block elements. The content of `pre`, `textarea`, `script` and `style` is
+/// kept as is. The whitespace added between block elements doesn't change how a page is displayed,
+/// except around inline-block elements styled so, or in elements with `white-space: pre`. The
+/// whitespace between inline content is kept, collapsed to a space.
+///
+/// `markup` is rendered like in `html!`, e.g. a `&str` is escaped as text.
+pub fn pretty(markup: &(impl Render + ?Sized)) -> Markup {
+    let html = markup.markup().into_inner();
+    let nodes = parse(&html);
+    let mut out = String::with_capacity(html.len() * 2);
+    write_block_content(&nodes, 0, &mut out);
+    Escaped::from_escaped(out)
+}
+
+const INDENT: &str = "  ";
+
+/// Elements whose content is copied as is.
+const VERBATIM_ELEMENTS: &[&str] = &["pre", "script", "style", "textarea"];
+
+/// Phrasing elements which are nonetheless given their own line, as they're mostly found in the
+/// `<head>`.
+const BLOCK_PHRASING_ELEMENTS: &[&str] = &["link", "meta", "noscript", "script", "template"];
+
+#[derive(Debug)]
+enum Node<'a> {
+    Element {
+        name: &'a str,
+        open: &'a str,
+        children: Vec<Node<'a>>,
+        close: Option<&'a str>,
+    },
+    Text(&'a str),
+    /// Comments and doctypes.
+    Other(&'a str),
+}
+
+impl Node<'_> {
+    fn is_block(&self) -> bool {
+        match self {
+            Node::Element { name, children, .. } => {
+                let phrasing = lfml_html5::element(name).is_some_and(|e| e.phrasing);
+                !phrasing
+                    || BLOCK_PHRASING_ELEMENTS.contains(name)
+                    || children.iter().any(Node::is_block)
+            }
+            Node::Text(_) => false,
+            Node::Other(_) => true,
+        }
+    }
+}
+
+/// Parses `html` into a tree, closing elements left open where their parent closes. Closing tags
+/// which don't match an open element are kept as text.
+fn parse(html: &str) -> Vec<Node<'_>> {
+    // the elements being parsed, with the children parsed so far.
+    let mut stack: Vec<(&str, &str, Vec<Node>)> = vec![];
+    let mut root = vec![];
+
+    fn close<'a>(
+        stack: &mut Vec<(&'a str, &'a str, Vec<Node<'a>>)>,
+        root: &mut Vec<Node<'a>>,
+        close: Option<&'a str>,
+    ) {
+        if let Some((name, open, children)) = stack.pop() {
+            let element = Node::Element {
+                name,
+                open,
+                children,
+                close,
+            };
+            match stack.last_mut() {
+                Some((_, _, siblings)) => siblings.push(element),
+                None => root.push(element),
+            }
+        }
+    }
+
+    let mut rest = html;
+    while let Some(token) = next_token(&mut rest) {
+        match token {
+            Token::Open { name, open } => stack.push((name, open, vec![])),
+            Token::Close { name, close: tag } => {
+                match stack
+                    .iter()
+                    .rposition(|(n, ..)| n.eq_ignore_ascii_case(name))
+                {
+                    Some(i) => {
+                        while stack.len() > i + 1 {
+                            close(&mut stack, &mut root, None);
+                        }
+                        close(&mut stack, &mut root, Some(tag));
+                    }
+                    None => match stack.last_mut() {
+                        Some((_, _, children)) => children.push(Node::Text(tag)),
+                        None => root.push(Node::Text(tag)),
+                    },
+                }
+            }
+            Token::Node(node) => match stack.last_mut() {
+                Some((_, _, children)) => children.push(node),
+                None => root.push(node),
+            },
+        }
+    }
+
+    while !stack.is_empty() {
+        close(&mut stack, &mut root, None);
+    }
+    root
+}
+
+enum Token<'a> {
+    Open {
+        name: &'a str,
+        open: &'a str,
+    },
+    Close {
+        name: &'a str,
+        close: &'a str,
+    },
+    /// Text, comments, doctypes, and elements without content to parse: void and verbatim ones.
+    Node(Node<'a>),
+}
+
+/// Takes the next token off the start of `rest`.
+fn next_token<'a>(rest: &mut &'a str) -> Option<Token<'a>> {
+    let html = *rest;
+    if html.is_empty() {
+        return None;
+    }
+
+    let (token, len) = if html.starts_with("<!") {
+        let end = match html.strip_prefix("<!--") {
+            Some(comment) => comment.find("-->").map_or(html.len(), |i| i + 7),
+            None => html.find('>').map_or(html.len(), |i| i + 1),
+        };
+        (Token::Node(Node::Other(&html[..end])), end)
+    } else if let Some(tag) = html.strip_prefix("</") {
+        let end = html.find('>').map_or(html.len(), |i| i + 1);
+        let name = tag[..tag.find('>').unwrap_or(tag.len())].trim();
+        (
+            Token::Close {
+                name,
+                close: &html[..end],
+            },
+            end,
+        )
+    } else if html.starts_with('<') && html[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+        let end = tag_end(html);
+        let open = &html[..end];
+        let name_end = open[1..]
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .map_or(open.len(), |i| i + 1);
+        let name = &open[1..name_end];
+
+        if lfml_html5::is_void_element(name) || open.ends_with("/>") {
+            let element = Node::Element {
+                name,
+                open,
+                children: vec![],
+                close: None,
+            };
+            (Token::Node(element), end)
+        } else if VERBATIM_ELEMENTS.contains(&name) {
+            let content = &html[end..];
+            let content_end = find_close_tag(content, name);
+            let close_end = content[content_end..]
+                .find('>')
+                .map_or(content.len(), |i| content_end + i + 1);
+            let element = Node::Element {
+                name,
+                open,
+                children: match &content[..content_end] {
+                    "" => vec![],
+                    text => vec![Node::Text(text)],
+                },
+                close: Some(&content[content_end..close_end]).filter(|c| !c.is_empty()),
+            };
+            (Token::Node(element), end + close_end)
+        } else {
+            (Token::Open { name, open }, end)
+        }
+    } else {
+        let end = html.as_bytes()[1..]
+            .iter()
+            .position(|&b| b == b'<')
+            .map_or(html.len(), |i| i + 1);
+        (Token::Node(Node::Text(&html[..end])), end)
+    };
+
+    *rest = &html[len..];
+    Some(token)
+}
+
+/// The length of the opening tag at the start of `html`, skipping over quoted attribute values.
+fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return i + 1,
+            _ => {}
+        }
+    }
+    html.len()
+}
+
+/// The index of the `</name` ending a verbatim element, or the end of `html`.
+fn find_close_tag(html: &str, name: &str) -> usize {
+    html.match_indices("</")
+        .map(|(i, _)| i)
+        .find(|&i| {
+            html.get(i + 2..i + 2 + name.len())
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+        .unwrap_or(html.len())
+}
+
+fn new_line(depth: usize, out: &mut String) {
+    if !out.is_empty() {
+        out.truncate(out.trim_end_matches([' ', '\t']).len());
+        out.push('\n');
+    }
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+/// Writes `nodes` with each block on its own line, and the inline nodes between them together.
+fn write_block_content(nodes: &[Node], depth: usize, out: &mut String) {
+    let mut line_start = true;
+    for node in nodes {
+        match node {
+            // whitespace at the start of a line isn't displayed, and what's at its end is trimmed.
+            Node::Text(t) if t.trim().is_empty() => {
+                if !line_start {
+                    out.push(' ');
+                }
+            }
+            Node::Text(t) if line_start => {
+                new_line(depth, out);
+                out.push_str(t.trim_start());
+                line_start = false;
+            }
+            node if node.is_block() => {
+                new_line(depth, out);
+                write_block(node, depth, out);
+                line_start = true;
+            }
+            node => {
+                if line_start {
+                    new_line(depth, out);
+                    line_start = false;
+                }
+                write_inline(node, out);
+            }
+        }
+    }
+}
+
+fn write_block(node: &Node, depth: usize, out: &mut String) {
+    match node {
+        Node::Element {
+            name,
+            open,
+            children,
+            close,
+        } => {
+            out.push_str(open);
+            if VERBATIM_ELEMENTS.contains(name) || !children.iter().any(Node::is_block) {
+                children.iter().for_each(|c| write_inline(c, out));
+            } else {
+                write_block_content(children, depth + 1, out);
+                if close.is_some() {
+                    new_line(depth, out);
+                }
+            }
+            out.push_str(close.unwrap_or_default());
+        }
+        Node::Text(t) | Node::Other(t) => out.push_str(t),
+    }
+}
+
+fn write_inline(node: &Node, out: &mut String) {
+    match node {
+        Node::Element {
+            open,
+            children,
+            close,
+            ..
+        } => {
+            out.push_str(open);
+            children.iter().for_each(|c| write_inline(c, out));
+            out.push_str(close.unwrap_or_default());
+        }
+        Node::Text(t) | Node::Other(t) => out.push_str(t),
+    }
+}
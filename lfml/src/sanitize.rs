@@ -0,0 +1,37 @@
+use lfml_escape::sanitize::Sanitizer;
+
+use crate::{Escaped, Markup};
+
+/// Sanitizes html written by users, e.g. comments or rich text, into markup which is safe to
+/// insert in a template:
+///
+/// ```
+/// let comment = r#"<p onclick="steal()">Nice <b>post<script>steal()</script></p><a href="javascript:steal()">!"#;
+///
+/// assert_eq!(
+///     lfml::html! { article { (lfml::sanitize(comment)) } }.into_inner(),
+///     "<article><p>Nice <b>post</b></p><a>!</a></article>",
+/// );
+/// ```
+///
+/// The tags, attributes and url schemes which are kept are listed in
+/// [`lfml::escape::sanitize`](crate::escape::sanitize), see [`sanitize_with`] to change them.
+pub fn sanitize(html: &str) -> Markup {
+    Escaped::from_escaped(lfml_escape::sanitize::sanitize_string(html))
+}
+
+/// Sanitizes `html` with the allowlist of `sanitizer`:
+///
+/// ```
+/// use lfml::escape::sanitize::Sanitizer;
+///
+/// let sanitizer = Sanitizer::new().deny_tag("img").allow_attribute("*", "id");
+///
+/// assert_eq!(
+///     lfml::sanitize_with(&sanitizer, r#"<h2 id="intro">Hi</h2><img src="/a.png">"#).into_inner(),
+///     r#"<h2 id="intro">Hi</h2>"#,
+/// );
+/// ```
+pub fn sanitize_with(sanitizer: &Sanitizer, html: &str) -> Markup {
+    Escaped::from_escaped(sanitizer.sanitize_string(html))
+}
@@ -1,2 +1,9 @@
 pub mod attrs;
+pub mod capacity;
+pub mod classes;
+pub mod io;
+pub mod join;
+pub mod looped;
 pub mod markup;
+pub mod slot;
+pub mod style;
@@ -1,17 +1,308 @@
+use std::{borrow::Cow, fmt, ops::Range};
+
+use lfml_escape::{url, EscapeWriter};
+
+use crate::Render;
+
 pub trait Spread {
-    fn raw(&self) -> String;
+    /// Adds the attributes to `attrs`, e.g. a `String` they're written into as ` href="/" hidden`.
+    fn spread_to(&self, attrs: &mut dyn AttributeSink);
+
+    /// The attributes added by [`Spread::spread_to`], written as ` href="/" hidden`.
+    fn raw(&self) -> String {
+        let mut buf = String::new();
+        self.spread_to(&mut buf);
+        buf
+    }
+}
+
+/// What a [`Spread`] adds its attributes to: a `String` or an [`AttributeWriter`] they're written
+/// into, or the [`Attributes`] of the tag it's spread onto.
+pub trait AttributeSink {
+    /// Adds the attribute `name`, without a value when `value` is `None`. The value is written as
+    /// is, it's up to the caller to escape it for a double quoted attribute value.
+    fn attribute(&mut self, name: &str, value: Option<fmt::Arguments<'_>>);
+}
+
+impl AttributeSink for String {
+    fn attribute(&mut self, name: &str, value: Option<fmt::Arguments<'_>>) {
+        // Writing into a `String` never fails.
+        let _ = write_attribute(self, name, value);
+    }
+}
+
+/// An [`AttributeSink`] writing the attributes straight into `W`, as ` href="/" hidden`, which
+/// `html!` spreads into when there's no other attribute on the tag for them to conflict with.
+pub struct AttributeWriter<'a, W: ?Sized> {
+    output: &'a mut W,
+    result: fmt::Result,
+}
+
+impl<'a, W: fmt::Write + ?Sized> AttributeWriter<'a, W> {
+    pub fn new(output: &'a mut W) -> Self {
+        Self {
+            output,
+            result: Ok(()),
+        }
+    }
+
+    /// The error of the first write which failed, the attributes after it being left out.
+    pub fn finish(self) -> fmt::Result {
+        self.result
+    }
+}
+
+impl<W: fmt::Write + ?Sized> AttributeSink for AttributeWriter<'_, W> {
+    fn attribute(&mut self, name: &str, value: Option<fmt::Arguments<'_>>) {
+        if self.result.is_ok() {
+            self.result = write_attribute(self.output, name, value);
+        }
+    }
+}
+
+fn write_attribute<W: fmt::Write + ?Sized>(
+    output: &mut W,
+    name: &str,
+    value: Option<fmt::Arguments<'_>>,
+) -> fmt::Result {
+    output.write_char(' ')?;
+    output.write_str(name)?;
+    if let Some(value) = value {
+        output.write_str("=\"")?;
+        if url::is_url_attribute(name) {
+            let value = match value.as_str() {
+                Some(v) => Cow::Borrowed(v),
+                None => Cow::Owned(value.to_string()),
+            };
+            output.write_str(safe_url(&value))?;
+        } else {
+            output.write_fmt(value)?;
+        }
+        output.write_char('"')?;
+    }
+    Ok(())
+}
+
+/// The escaped url `value`, or [`url::UNSAFE_URL`] if its scheme executes code, so that the url
+/// attributes written by a [`Spread`] are checked like those of the template.
+fn safe_url(value: &str) -> &str {
+    if url::is_safe_escaped(value) {
+        value
+    } else {
+        url::UNSAFE_URL
+    }
 }
 
 pub struct NameOnly;
 
-impl<'a, T: Spread> Spread for &'a T {
-    fn raw(&self) -> String {
-        Spread::raw(*self)
+impl<T: Spread + ?Sized> Spread for &T {
+    fn spread_to(&self, attrs: &mut dyn AttributeSink) {
+        Spread::spread_to(*self, attrs)
     }
 }
 
-impl<'a, T: Spread> Spread for &'a mut T {
-    fn raw(&self) -> String {
-        Spread::raw(*self)
+impl<T: Spread + ?Sized> Spread for &mut T {
+    fn spread_to(&self, attrs: &mut dyn AttributeSink) {
+        Spread::spread_to(*self, attrs)
+    }
+}
+
+/// What happens when a tag with a spread ends up with an attribute twice, chosen with
+/// `@conflicts(error)`, `@conflicts(last_wins)` or `@conflicts(merge)` among its attributes:
+///
+/// ```
+/// use lfml::{html, Spread};
+///
+/// #[derive(Spread)]
+/// struct Link<'a> {
+///     href: &'a str,
+///     class: &'a str,
+/// }
+///
+/// let link = Link { href: "/b", class: "active" };
+///
+/// assert_eq!(
+///     html! { a.nav href="/a" @(link) {} }.into_inner(),
+///     r#"<a class="nav active" href="/b"></a>"#,
+/// );
+/// assert_eq!(
+///     html! { a.nav href="/a" @(link) @conflicts(last_wins) {} }.into_inner(),
+///     r#"<a class="active" href="/b"></a>"#,
+/// );
+/// ```
+///
+/// The same attribute written twice in the template is a compile error instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Conflicts {
+    /// The value written first is kept and the later ones are dropped. A conflict is treated as
+    /// a bug in the template: debug builds fail a `debug_assert!` naming the attribute, so that
+    /// it shows up in tests, and release builds render the first value without any report.
+    ///
+    /// Attributes that are only set at times, like `title=[title]`, only conflict when they are
+    /// set. Use [`Conflicts::LastWins`] or [`Conflicts::Merge`] where a conflict is expected.
+    Error,
+    /// The value written last replaces the first one, in its place.
+    LastWins,
+    /// `class`es are joined with spaces and `style`s with semicolons, other attributes are
+    /// replaced like with [`Conflicts::LastWins`].
+    #[default]
+    Merge,
+}
+
+/// The attributes of a tag with a spread, collected so that the conflicts between the spread and
+/// the other attributes are resolved before the tag is written.
+#[derive(Debug, Clone)]
+pub struct Attributes<'a> {
+    conflicts: Conflicts,
+    attrs: Vec<(Text<'a>, Option<Text<'a>>)>,
+    /// The names and values which were formatted rather than given as strings, one after the
+    /// other, so that they don't take a `String` each.
+    buffer: String,
+}
+
+#[derive(Debug, Clone)]
+enum Text<'a> {
+    Str(Cow<'a, str>),
+    /// A range of [`Attributes::buffer`].
+    Buffered(Range<usize>),
+}
+
+impl<'a> Attributes<'a> {
+    pub fn new(conflicts: Conflicts) -> Self {
+        Self {
+            conflicts,
+            attrs: Vec::with_capacity(8),
+            buffer: String::with_capacity(128),
+        }
+    }
+
+    /// Adds an attribute, whose value is escaped already. The value of a url attribute is replaced
+    /// with [`url::UNSAFE_URL`] if its scheme executes code.
+    pub fn push(&mut self, name: impl Into<Cow<'a, str>>, value: Option<Cow<'a, str>>) {
+        self.insert(Text::Str(name.into()), value.map(Text::Str));
+    }
+
+    /// Adds an attribute whose value is formatted into a buffer shared by the attributes, escaped
+    /// by `escape`, e.g. `lfml::escape::attr::escape_to_writer`, as it's written. The value of a
+    /// url attribute is replaced with [`url::UNSAFE_URL`] if its scheme executes code.
+    pub fn push_fmt(
+        &mut self,
+        name: impl Into<Cow<'a, str>>,
+        value: fmt::Arguments<'_>,
+        escape: fn(&str, &mut String) -> fmt::Result,
+    ) {
+        let value = self.format(value, escape);
+        self.insert(Text::Str(name.into()), Some(value));
+    }
+
+    fn format(
+        &mut self,
+        value: fmt::Arguments<'_>,
+        escape: fn(&str, &mut String) -> fmt::Result,
+    ) -> Text<'a> {
+        let start = self.buffer.len();
+        // Writing into a `String` never fails.
+        let _ = fmt::Write::write_fmt(&mut EscapeWriter::new(&mut self.buffer, escape), value);
+        Text::Buffered(start..self.buffer.len())
+    }
+
+    fn get<'t>(&'t self, text: &'t Text<'_>) -> &'t str {
+        match text {
+            Text::Str(s) => s,
+            Text::Buffered(range) => &self.buffer[range.clone()],
+        }
+    }
+
+    fn insert(&mut self, name: Text<'a>, value: Option<Text<'a>>) {
+        let value = match value {
+            Some(v)
+                if url::is_url_attribute(self.get(&name))
+                    && !url::is_safe_escaped(self.get(&v)) =>
+            {
+                Some(Text::Str(Cow::Borrowed(url::UNSAFE_URL)))
+            }
+            v => v,
+        };
+        let Some(i) = self
+            .attrs
+            .iter()
+            .position(|(n, _)| self.get(n).eq_ignore_ascii_case(self.get(&name)))
+        else {
+            self.attrs.push((name, value));
+            return;
+        };
+
+        let name = self.get(&name);
+        let separator = match self.conflicts {
+            Conflicts::Error => {
+                debug_assert!(false, "the attribute `{name}` is set twice");
+                return;
+            }
+            Conflicts::LastWins => None,
+            Conflicts::Merge if name.eq_ignore_ascii_case("class") => Some(" "),
+            Conflicts::Merge if name.eq_ignore_ascii_case("style") => Some("; "),
+            Conflicts::Merge => None,
+        };
+        let previous = self.attrs[i].1.take();
+        self.attrs[i].1 = match (separator, previous, value) {
+            (Some(separator), Some(p), Some(v))
+                if !self.get(&p).is_empty() && !self.get(&v).is_empty() =>
+            {
+                Some(self.join(&p, separator, &v))
+            }
+            (Some(_), p, None) => p,
+            (Some(_), Some(p), Some(v)) if self.get(&v).is_empty() => Some(p),
+            (_, _, v) => v,
+        };
+    }
+
+    /// `previous` and `value` joined by `separator`, at the end of the buffer.
+    fn join(&mut self, previous: &Text<'_>, separator: &str, value: &Text<'_>) -> Text<'a> {
+        let start = self.buffer.len();
+        self.push_text(previous);
+        let previous_len = self.buffer[start..].trim_end_matches([';', ' ']).len();
+        self.buffer.truncate(start + previous_len);
+        self.buffer.push_str(separator);
+        self.push_text(value);
+        Text::Buffered(start..self.buffer.len())
+    }
+
+    fn push_text(&mut self, text: &Text<'_>) {
+        match text {
+            Text::Str(s) => self.buffer.push_str(s),
+            Text::Buffered(range) => self.buffer.extend_from_within(range.clone()),
+        }
+    }
+}
+
+impl AttributeSink for Attributes<'_> {
+    fn attribute(&mut self, name: &str, value: Option<fmt::Arguments<'_>>) {
+        let start = self.buffer.len();
+        self.buffer.push_str(name);
+        let name = Text::Buffered(start..self.buffer.len());
+        let value = value.map(|v| match v.as_str() {
+            Some(v) => Text::Str(Cow::Borrowed(v)),
+            // the values of a spread are escaped already.
+            None => self.format(v, |s, buffer| {
+                buffer.push_str(s);
+                Ok(())
+            }),
+        });
+        self.insert(name, value);
+    }
+}
+
+impl Render for Attributes<'_> {
+    fn render_to<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        for (name, value) in &self.attrs {
+            w.write_char(' ')?;
+            w.write_str(self.get(name))?;
+            if let Some(value) = value {
+                w.write_str("=\"")?;
+                w.write_str(self.get(value))?;
+                w.write_char('"')?;
+            }
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,41 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The capacity an `html!` template allocates its output with, kept in a `static` per template
+/// when the `adaptive-capacity` feature is enabled.
+///
+/// It starts at the length of the template's literals, and then keeps an estimate of the length
+/// of its renders, so that a template rendering to much more than its literals only reallocates
+/// while it's first rendered. The estimate jumps up to longer renders and decays towards shorter
+/// ones by an eighth of the difference, so that a single short render doesn't make the next long
+/// one reallocate, and a single huge one doesn't stay reserved for good.
+#[derive(Debug)]
+pub struct Capacity {
+    static_len: usize,
+    estimate: AtomicUsize,
+}
+
+impl Capacity {
+    pub const fn new(static_len: usize) -> Self {
+        Self {
+            static_len,
+            estimate: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get(&self) -> usize {
+        self.static_len.max(self.estimate.load(Ordering::Relaxed))
+    }
+
+    /// Updates the estimate with the length of a render, for the next ones.
+    pub fn update(&self, len: usize) {
+        let _ = self
+            .estimate
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |estimate| {
+                Some(if len >= estimate {
+                    len
+                } else {
+                    estimate - (estimate - len) / 8
+                })
+            });
+    }
+}
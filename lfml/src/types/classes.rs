@@ -0,0 +1,111 @@
+use std::{borrow::Cow, fmt};
+
+/// Builds [`Classes`] from a list of classes, each of which may be followed by a condition it's
+/// only added under:
+///
+/// ```
+/// use lfml::{classes, html};
+///
+/// let (primary, disabled) = (true, false);
+///
+/// assert_eq!(
+///     html! {
+///         button.card.(classes! { "btn", "btn-primary" => primary, "disabled" => disabled }) {
+///             "Save"
+///         }
+///     }
+///     .into_inner(),
+///     r#"<button class="card btn btn-primary">Save</button>"#,
+/// );
+/// ```
+///
+/// Classes separated by whitespace are added one by one, and are only added once:
+///
+/// ```
+/// assert_eq!(lfml::classes! { "a b", "b", "c" => false }.to_string(), "a b");
+/// ```
+#[macro_export]
+macro_rules! classes {
+    (@condition) => {
+        true
+    };
+    (@condition $condition:expr) => {
+        $condition
+    };
+    ($($class:expr $(=> $condition:expr)?),* $(,)?) => {{
+        let mut classes = $crate::Classes::new();
+        $(
+            if $crate::classes!(@condition $($condition)?) {
+                classes.push($class);
+            }
+        )*
+        classes
+    }};
+}
+
+/// A list of distinct classes, which displays as the value of a `class` attribute, i.e. separated
+/// by spaces. See [`classes!`](crate::classes!).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Classes<'a>(Vec<Cow<'a, str>>);
+
+impl<'a> Classes<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds each of the whitespace separated `classes` which isn't in the list yet.
+    pub fn push(&mut self, classes: impl Into<Cow<'a, str>>) {
+        match classes.into() {
+            Cow::Borrowed(classes) => {
+                for class in classes.split_whitespace() {
+                    self.push_one(Cow::Borrowed(class));
+                }
+            }
+            Cow::Owned(classes) => {
+                for class in classes.split_whitespace() {
+                    self.push_one(Cow::Owned(class.to_owned()));
+                }
+            }
+        }
+    }
+
+    fn push_one(&mut self, class: Cow<'a, str>) {
+        if !self.0.contains(&class) {
+            self.0.push(class);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|c| &**c)
+    }
+}
+
+impl<'a, C: Into<Cow<'a, str>>> Extend<C> for Classes<'a> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|c| self.push(c));
+    }
+}
+
+impl<'a, C: Into<Cow<'a, str>>> FromIterator<C> for Classes<'a> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        let mut classes = Self::new();
+        classes.extend(iter);
+        classes
+    }
+}
+
+impl fmt::Display for Classes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, class) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(class)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+/// Adapts a `std::io::Write` into a `core::fmt::Write`, so that `html_to!` and
+/// `Render::render_to` can write straight into sockets and files.
+///
+/// `core::fmt::Error` carries no information, so the underlying `io::Error` is kept and can be
+/// recovered with [`IoWriter::take_error`].
+pub struct IoWriter<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write> IoWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Returns the `io::Error` behind the last failed write, if any.
+    pub fn take_error(&mut self) -> std::io::Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: std::io::Write> core::fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            core::fmt::Error
+        })
+    }
+}
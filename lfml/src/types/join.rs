@@ -0,0 +1,62 @@
+use crate::Render;
+
+/// Renders the items of `iter` with `separator` between each of them.
+///
+/// The separator is itself rendered, so it can be a `Markup` fragment as well as plain text:
+///
+/// ```
+/// let tags = ["rust", "html"];
+/// let links = tags.iter().map(|t| lfml::html! { a href=(format!("/tags/{t}")) { (t) } });
+///
+/// assert_eq!(
+///     lfml::html! { p { (lfml::join(links, lfml::html! { br; })) } }.into_inner(),
+///     r#"<p><a href="/tags/rust">rust</a><br><a href="/tags/html">html</a></p>"#,
+/// );
+/// ```
+///
+/// Rendering takes `&self`, so the iterator is cloned each time the `Join` is rendered.
+pub fn join<I, S>(iter: I, separator: S) -> Join<I::IntoIter, S>
+where
+    I: IntoIterator,
+    I::IntoIter: Clone,
+    I::Item: Render,
+    S: Render,
+{
+    Join {
+        iter: iter.into_iter(),
+        separator,
+    }
+}
+
+/// See [`join`].
+#[derive(Clone)]
+pub struct Join<I, S> {
+    iter: I,
+    separator: S,
+}
+
+impl<I, S> Render for Join<I, S>
+where
+    I: Iterator + Clone,
+    I::Item: Render,
+    S: Render,
+{
+    fn markup_to_string(&self, buf: &mut String) {
+        for (i, x) in self.iter.clone().enumerate() {
+            if i > 0 {
+                self.separator.markup_to_string(buf);
+            }
+            x.markup_to_string(buf);
+        }
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        for (i, x) in self.iter.clone().enumerate() {
+            if i > 0 {
+                self.separator.render_to(w)?;
+            }
+            x.render_to(w)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use core::iter::Peekable;
+
+/// Where an iteration of `@for #context, #pattern in #iter` is in the loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    /// Index of the iteration, from 0.
+    pub index: usize,
+    pub first: bool,
+    pub last: bool,
+}
+
+impl Loop {
+    /// Whether the index is even, i.e. this is the 1st, 3rd, 5th... iteration.
+    pub fn is_even(&self) -> bool {
+        self.index & 1 == 0
+    }
+
+    pub fn is_odd(&self) -> bool {
+        !self.is_even()
+    }
+}
+
+/// Pairs each item of `iter` with its [`Loop`], which is what `@for l, item in iter` iterates.
+///
+/// ```
+/// let items = ["a", "b", "c"];
+///
+/// assert_eq!(
+///     lfml::html! {
+///         @for l, i in items {
+///             (i) @if !l.last { ", " }
+///         }
+///     }
+///     .into_inner(),
+///     "a, b, c",
+/// );
+/// ```
+pub fn looped<I: IntoIterator>(iter: I) -> Looped<I::IntoIter> {
+    Looped {
+        iter: iter.into_iter().peekable(),
+        index: 0,
+    }
+}
+
+/// See [`looped`].
+pub struct Looped<I: Iterator> {
+    iter: Peekable<I>,
+    index: usize,
+}
+
+impl<I: Iterator> Iterator for Looped<I> {
+    type Item = (Loop, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let l = Loop {
+            index: self.index,
+            first: self.index == 0,
+            last: self.iter.peek().is_none(),
+        };
+        self.index += 1;
+        Some((l, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
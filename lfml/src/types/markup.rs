@@ -1,11 +1,103 @@
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+/// Html which `html!` or this crate escaped, and which is rendered as is.
+///
+/// To insert html which is trusted without having been escaped, use [`raw`]. With the
+/// `sealed-escaped` feature, `Escaped` can't be built outside of this crate and `html!`, so that
+/// [`raw`] is the only way to do so.
+#[cfg(not(feature = "sealed-escaped"))]
 pub struct Escaped<T>(pub T);
 
+/// Html which `html!` or this crate escaped, and which is rendered as is.
+///
+/// To insert html which is trusted without having been escaped, use [`raw`]: with the
+/// `sealed-escaped` feature, `Escaped` can't be built outside of this crate and `html!`.
+///
+/// The seal guards against mistakes, it isn't a security boundary: the code `html!` generates is
+/// compiled as part of the calling crate, so what it does to build an `Escaped` can be written by
+/// hand too, with the `#[doc(hidden)]` items which aren't public API. Review their uses along with
+/// those of [`raw`].
+#[cfg(feature = "sealed-escaped")]
+pub struct Escaped<T>(pub(crate) T);
+
+/// Not public API, passed by the code `html!` generates to [`Escaped::__from_html`], so that it
+/// isn't called by mistake.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct __FromHtml(());
+
+impl __FromHtml {
+    #[doc(hidden)]
+    pub const fn __lfml_macro_only() -> Self {
+        Self(())
+    }
+}
+
+impl<T> Escaped<T> {
+    /// Not public API, the constructor used by the code `html!` generates.
+    #[doc(hidden)]
+    pub const fn __from_html(html: T, _: __FromHtml) -> Self {
+        Self(html)
+    }
+
+    /// The constructor for the html this crate escaped, which is the only one left with the
+    /// `sealed-escaped` feature.
+    pub(crate) const fn from_escaped(html: T) -> Self {
+        Self(html)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    pub fn as_inner(&self) -> &T {
+        &self.0
+    }
+}
+
 impl<T: std::fmt::Display> Escaped<T> {
     pub fn as_string(&self) -> String {
         self.0.to_string()
     }
 }
 
+/// Html which is trusted to be safe without escaping, see [`raw`].
+pub struct PreEscaped<T>(pub T);
+
+/// Marks `html` as trusted, so that it's rendered without escaping:
+///
+/// ```
+/// let icon = lfml::raw("<svg></svg>");
+///
+/// assert_eq!(lfml::html! { button { (icon) "Save" } }.into_inner(), "<button><svg></svg>Save</button>");
+/// ```
+///
+/// Only use it on html which doesn't come from users. With the `audit-raw` feature, `html!` warns
+/// about every `PreEscaped` value it inserts, to review each of them.
+///
+/// The audit only sees the type of the value written in the template, a `PreEscaped` or a
+/// reference to one: raw html inside other types, like a `Box<PreEscaped<_>>`, an
+/// `Option<PreEscaped<_>>` or a `Vec<PreEscaped<_>>`, or inside a [`Render`] impl, isn't warned
+/// about, nor is the output of `pretty` or `sanitize`, which is an [`Escaped`].
+pub const fn raw<T>(html: T) -> PreEscaped<T> {
+    PreEscaped(html)
+}
+
+/// Not public API. With the `audit-raw` feature, `html!` calls `__lfml_audit_raw` on every value
+/// it inserts, which resolves to the deprecated inherent method of `PreEscaped` before this one.
+#[doc(hidden)]
+pub trait __AuditRaw {
+    fn __lfml_audit_raw(&self) {}
+}
+
+impl<T: ?Sized> __AuditRaw for T {}
+
+impl<T> PreEscaped<T> {
+    #[doc(hidden)]
+    #[deprecated(note = "raw html is inserted here, make sure it can't come from users")]
+    pub fn __lfml_audit_raw(&self) {}
+}
+
 pub trait Render {
     fn markup(&self) -> Escaped<String> {
         let mut buf = String::new();
@@ -14,7 +106,20 @@ pub trait Render {
     }
 
     fn markup_to_string(&self, buf: &mut String) {
-        buf.push_str(&self.markup().as_string())
+        // Writing into a `String` never fails.
+        let _ = self.render_to(buf);
+    }
+
+    /// Renders straight into any `core::fmt::Write` sink, without an intermediate `String`.
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        w.write_str(&self.markup().0)
+    }
+
+    /// An estimate of the number of bytes this renders to, which `html!` reserves before pushing
+    /// it. It should be cheap to compute, and rather too low than too high: escaping can only add
+    /// to it.
+    fn size_hint(&self) -> usize {
+        0
     }
 }
 
@@ -22,32 +127,233 @@ impl<T: std::fmt::Display> Render for Escaped<T> {
     fn markup_to_string(&self, buf: &mut String) {
         buf.push_str(&self.as_string())
     }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        write!(w, "{}", self.0)
+    }
+}
+
+impl<T: std::fmt::Display> Render for PreEscaped<T> {
+    fn markup_to_string(&self, buf: &mut String) {
+        use std::fmt::Write;
+        // Writing into a `String` never fails.
+        let _ = write!(buf, "{}", self.0);
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        write!(w, "{}", self.0)
+    }
 }
 
 impl Render for str {
     fn markup_to_string(&self, buf: &mut String) {
         lfml_escape::escape_to_string(self, buf);
     }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        lfml_escape::escape_to_writer(self, w)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl Render for String {
     fn markup_to_string(&self, buf: &mut String) {
         str::markup_to_string(self, buf)
     }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        str::render_to(self, w)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
-impl<'a, T: Render + ?Sized> Render for &'a T {
+impl<T: Render + ?Sized> Render for &T {
     fn markup_to_string(&self, buf: &mut String) {
         T::markup_to_string(self, buf);
     }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        T::render_to(self, w)
+    }
+
+    fn size_hint(&self) -> usize {
+        T::size_hint(self)
+    }
 }
 
-impl<'a, T: Render + ?Sized> Render for &'a mut T {
+impl<T: Render + ?Sized> Render for &mut T {
     fn markup_to_string(&self, buf: &mut String) {
         T::markup_to_string(self, buf);
     }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        T::render_to(self, w)
+    }
+
+    fn size_hint(&self) -> usize {
+        T::size_hint(self)
+    }
+}
+
+/// Renders every item, one after the other.
+impl<T: Render> Render for [T] {
+    fn markup_to_string(&self, buf: &mut String) {
+        for x in self {
+            x.markup_to_string(buf);
+        }
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        self.iter().try_for_each(|x| x.render_to(w))
+    }
+
+    fn size_hint(&self) -> usize {
+        self.iter().map(T::size_hint).sum()
+    }
+}
+
+impl<T: Render, const N: usize> Render for [T; N] {
+    fn markup_to_string(&self, buf: &mut String) {
+        <[T]>::markup_to_string(self, buf);
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        <[T]>::render_to(self, w)
+    }
+
+    fn size_hint(&self) -> usize {
+        <[T]>::size_hint(self)
+    }
 }
 
+impl<T: Render> Render for Vec<T> {
+    fn markup_to_string(&self, buf: &mut String) {
+        <[T]>::markup_to_string(self, buf);
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        <[T]>::render_to(self, w)
+    }
+
+    fn size_hint(&self) -> usize {
+        <[T]>::size_hint(self)
+    }
+}
+
+macro_rules! impl_render_for_smart_pointers {
+    ($($ty:ident)*) => {
+        $(
+            impl<T: Render + ?Sized> Render for $ty<T> {
+                fn markup_to_string(&self, buf: &mut String) {
+                    T::markup_to_string(self, buf);
+                }
+
+                fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+                    T::render_to(self, w)
+                }
+
+                fn size_hint(&self) -> usize {
+                    T::size_hint(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_render_for_smart_pointers! { Box Rc Arc }
+
+impl<B: Render + ToOwned + ?Sized> Render for Cow<'_, B> {
+    fn markup_to_string(&self, buf: &mut String) {
+        B::markup_to_string(self, buf);
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        B::render_to(self, w)
+    }
+
+    fn size_hint(&self) -> usize {
+        B::size_hint(self)
+    }
+}
+
+/// `None` renders nothing.
+impl<T: Render> Render for Option<T> {
+    fn markup_to_string(&self, buf: &mut String) {
+        if let Some(x) = self {
+            x.markup_to_string(buf);
+        }
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        match self {
+            Some(x) => x.render_to(w),
+            None => Ok(()),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, T::size_hint)
+    }
+}
+
+impl Render for bool {
+    fn markup_to_string(&self, buf: &mut String) {
+        buf.push_str(if *self { "true" } else { "false" });
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        w.write_str(if *self { "true" } else { "false" })
+    }
+
+    fn size_hint(&self) -> usize {
+        if *self {
+            4
+        } else {
+            5
+        }
+    }
+}
+
+impl Render for char {
+    fn markup_to_string(&self, buf: &mut String) {
+        str::markup_to_string(self.encode_utf8(&mut [0; 4]), buf)
+    }
+
+    fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        str::render_to(self.encode_utf8(&mut [0; 4]), w)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len_utf8()
+    }
+}
+
+/// Floats are formatted with `ryu`: the shortest representation which roundtrips, e.g. `1.0`,
+/// `0.1` or `1e21`. `NaN` and the infinities render as `NaN`, `inf` and `-inf`.
+macro_rules! impl_render_for_float_types {
+    ($($ty:ty)*) => {
+        $(
+            impl Render for $ty {
+                fn markup_to_string(&self, w: &mut String) {
+                    w.push_str(ryu::Buffer::new().format(*self));
+                }
+
+                fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+                    w.write_str(ryu::Buffer::new().format(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_render_for_float_types! { f32 f64 }
+
 macro_rules! impl_render_for_integer_types {
     ($($ty:ty)*) => {
         $(
@@ -55,6 +361,10 @@ macro_rules! impl_render_for_integer_types {
                 fn markup_to_string(&self, w: &mut String) {
                     w.push_str(itoa::Buffer::new().format(*self));
                 }
+
+                fn render_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+                    w.write_str(itoa::Buffer::new().format(*self))
+                }
             }
         )*
     };
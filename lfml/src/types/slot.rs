@@ -0,0 +1,114 @@
+use core::fmt;
+
+use crate::Render;
+
+/// A named region of a layout, filled in by the page using it.
+///
+/// A layout is a `#[component]` taking `Slot`s, which it places with `@slot name;`, or with
+/// `@slot name { ... }` to give markup rendered when the page leaves the slot empty:
+///
+/// ```
+/// use lfml::{component, html, Markup, Slot};
+///
+/// #[component]
+/// fn Base(title: &str, head: Slot, content: Slot) -> Markup {
+///     html! {
+///         html {
+///             head { title { (title) } @slot head { meta charset="utf-8"; } }
+///             body { @slot content; }
+///         }
+///     }
+/// }
+///
+/// let page = html! {
+///     Base title="Home" {
+///         @slot content { h1 { "Welcome" } }
+///     }
+/// };
+///
+/// assert_eq!(
+///     page.into_inner(),
+///     concat!(
+///         "<html><head><title>Home</title><meta charset=\"utf-8\"></head>",
+///         "<body><h1>Welcome</h1></body></html>"
+///     ),
+/// );
+/// ```
+///
+/// The page fills slots with `@slot name { ... }` as the children of the layout; the slots it
+/// doesn't fill keep their defaults, and filling a slot the layout doesn't declare is a compile
+/// error. A filled slot is rendered straight into the layout's output, without an intermediate
+/// `String`.
+///
+/// The fills are closures which the layout may call any number of times, so the values they
+/// interpolate are borrowed rather than moved, e.g. `(&title)` instead of `(title)`.
+#[derive(Clone, Copy, Default)]
+pub struct Slot<'a>(Option<&'a SlotFn<'a>>);
+
+type SlotFn<'a> = dyn Fn(&mut dyn fmt::Write) -> fmt::Result + 'a;
+
+impl<'a> Slot<'a> {
+    pub fn new(render: &'a SlotFn<'a>) -> Self {
+        Self(Some(render))
+    }
+
+    /// Whether the page filled this slot.
+    pub fn is_filled(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+impl Render for Slot<'_> {
+    fn markup_to_string(&self, buf: &mut String) {
+        if let Some(render) = self.0 {
+            // Writing into a `String` never fails.
+            let _ = render(buf);
+        }
+    }
+
+    fn render_to<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        match self.0 {
+            Some(render) => render(&mut DynWriter(w)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Lets an unsized writer be passed on as a `&mut dyn fmt::Write`.
+struct DynWriter<'a, W: ?Sized>(&'a mut W);
+
+impl<W: fmt::Write + ?Sized> fmt::Write for DynWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+/// Not public API. Calls a component from `html!` when the page fills none of its slots: with only
+/// the props if it declares no slots, and with its default slots otherwise.
+#[doc(hidden)]
+pub trait __Component<P, Args> {
+    type Output;
+
+    fn __lfml_call(self, props: P) -> Self::Output;
+}
+
+impl<F: FnOnce(P) -> R, P, R> __Component<P, (P,)> for F {
+    type Output = R;
+
+    fn __lfml_call(self, props: P) -> R {
+        self(props)
+    }
+}
+
+impl<F: FnOnce(P, S) -> R, P, S: Default, R> __Component<P, (P, S)> for F {
+    type Output = R;
+
+    fn __lfml_call(self, props: P) -> R {
+        self(props, S::default())
+    }
+}
+
+#[doc(hidden)]
+pub fn __call_component<F: __Component<P, Args>, P, Args>(component: F, props: P) -> F::Output {
+    component.__lfml_call(props)
+}
@@ -0,0 +1,64 @@
+use std::{borrow::Cow, fmt};
+
+/// The declarations of a `style` attribute, which displays as e.g. `color: red; margin: 0`.
+///
+/// `html!` builds one from `style { ... }` among the attributes of a tag, in which each property
+/// is given a literal, an `(expr)`, or an `[option]` which leaves the property out when `None`:
+///
+/// ```
+/// let (color, width) = ("red", None::<&str>);
+///
+/// assert_eq!(
+///     lfml::html! { p style { color: (color); margin: "0 auto"; width: [width] } {} }.into_inner(),
+///     r#"<p style="color: red; margin: 0 auto"></p>"#,
+/// );
+/// ```
+///
+/// The `style` attribute is left out when every property is left out.
+///
+/// The values are escaped so that they can't end their declaration, e.g. `red; position: fixed`
+/// stays the value of its property.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style<'a>(Vec<(Cow<'a, str>, String)>);
+
+impl<'a> Style<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the property `name`, replacing its previous value.
+    pub fn push(&mut self, name: impl Into<Cow<'a, str>>, value: impl fmt::Display) {
+        let name = name.into();
+        let value = lfml_escape::css_value::escape_string(&value.to_string());
+
+        match self.0.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, v)) => *v = value,
+            None => self.0.push((name, value)),
+        }
+    }
+
+    /// Sets the property `name` unless `value` is `None`.
+    pub fn push_option(&mut self, name: impl Into<Cow<'a, str>>, value: Option<impl fmt::Display>) {
+        if let Some(value) = value {
+            self.push(name, value);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Style<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            f.write_str(&lfml_escape::css_value::escape_string(name))?;
+            f.write_str(": ")?;
+            f.write_str(value)?;
+        }
+        Ok(())
+    }
+}
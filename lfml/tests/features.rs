@@ -0,0 +1,16 @@
+//! Compile-time checks made by optional features, which run along with the rest of the tests when
+//! the feature is on, e.g. with `cargo test -p lfml --features sealed-escaped`.
+
+#[cfg(feature = "sealed-escaped")]
+#[test]
+fn sealed_escaped() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/sealed_escaped/*.rs");
+}
+
+#[cfg(feature = "audit-raw")]
+#[test]
+fn audit_raw() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/audit_raw/*.rs");
+}
@@ -1,6 +1,7 @@
 use crate::assert_html_eq;
 
 #[test]
+#[cfg(not(feature = "strict"))]
 fn without_value() {
     assert_html_eq!({
         a foo {}
@@ -12,6 +13,7 @@ fn without_value() {
 }
 
 #[test]
+#[cfg(not(feature = "strict"))]
 fn with_value() {
     assert_html_eq!({
         a foo="bar" {}
@@ -23,6 +25,7 @@ fn with_value() {
 }
 
 #[test]
+#[cfg(not(feature = "strict"))]
 fn mix_of_two_types_of_attrs() {
     assert_html_eq!({
         a foo="bar" baz {}
@@ -34,29 +37,31 @@ fn mix_of_two_types_of_attrs() {
 }
 
 #[test]
+#[cfg(not(feature = "strict"))]
 fn self_closing_tags() {
     assert_html_eq!({
         a foo;
-    } => "<a foo>");
+    } => "<a foo></a>");
 
     assert_html_eq!({
         a foo; b {}
-    } => "<a foo><b></b>");
+    } => "<a foo></a><b></b>");
 
     assert_html_eq!({
         a foo="bar"; b {}
-    } => "<a foo=\"bar\"><b></b>");
+    } => "<a foo=\"bar\"></a><b></b>");
 }
 
 #[test]
+#[cfg(not(feature = "strict"))]
 fn attr_names_with_hyphens() {
     assert_html_eq!({
         a foo-bar;
-    } => "<a foo-bar>");
+    } => "<a foo-bar></a>");
 
     assert_html_eq!({
         a-b foo-bar="baz";
-    } => "<a-b foo-bar=\"baz\">");
+    } => "<a-b foo-bar=\"baz\"></a-b>");
 }
 
 #[test]
@@ -67,5 +72,14 @@ fn attr_id_and_class() {
 
     assert_html_eq!({
         a-b foo-bar="baz";
-    } => "<a-b foo-bar=\"baz\">");
+    } => "<a-b foo-bar=\"baz\"></a-b>");
+}
+
+#[test]
+fn literal_braces_next_to_interpolated_attrs() {
+    let x = "y";
+
+    assert_html_eq!({
+        a data-tpl="{}" title=(x) data-obj="{{x}}" {}
+    } => "<a data-tpl=\"{}\" title=\"y\" data-obj=\"{{x}}\"></a>");
 }
@@ -50,6 +50,13 @@ fn self_closing_tag_repeated() {
     assert_html_eq!({ link; link; } => "<link><link>");
 }
 
+#[test]
+fn non_void_tags_are_always_closed() {
+    assert_html_eq!({ div; } => "<div></div>");
+
+    assert_html_eq!({ p; "text" br; img src="a.png"; } => "<p></p>text<br><img src=\"a.png\">");
+}
+
 #[test]
 fn idents_with_hyphens_in_names() {
     // assert!(false);
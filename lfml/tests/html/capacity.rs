@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+
+use lfml::{html, Capacity, Markup, Render};
+
+#[test]
+fn literals_are_sized_exactly() {
+    let m = html! {
+        div #main { p { "Hello, " "world" } br; }
+    };
+
+    assert_eq!(
+        m.as_inner(),
+        "<div id=\"main\"><p>Hello, world</p><br></div>"
+    );
+    assert_eq!(m.as_inner().capacity(), m.as_inner().len());
+}
+
+#[test]
+fn exhaustive_branches_are_sized_by_the_shortest() {
+    fn render(x: Option<bool>) -> Markup {
+        html! {
+            @if let Some(true) = x {
+                "yes"
+            } @else if x.is_some() {
+                "no!"
+            } @else {
+                "unknown"
+            }
+            @match x {
+                Some(_) => { "." }
+                None => { "?" }
+            }
+        }
+    }
+
+    let m = render(Some(true));
+    assert_eq!(m.as_inner(), "yes.");
+    assert_eq!(m.as_inner().capacity(), m.as_inner().len());
+
+    // without an `@else`, nothing might be pushed.
+    let m = html! { @if m.as_inner().is_empty() { "empty" } };
+    assert_eq!(m.as_inner().capacity(), 0);
+}
+
+#[test]
+fn size_hints() {
+    assert_eq!("a<b".size_hint(), 3);
+    assert_eq!(String::from("abc").size_hint(), 3);
+    assert_eq!(Cow::Borrowed("abcd").size_hint(), 4);
+    assert_eq!(Some("ab").size_hint(), 2);
+    assert_eq!(None::<&str>.size_hint(), 0);
+    assert_eq!(["ab", "cd"].size_hint(), 4);
+    assert_eq!(vec![Box::new("abc")].size_hint(), 3);
+    assert_eq!('é'.size_hint(), 2);
+    assert_eq!(false.size_hint(), 5);
+}
+
+#[test]
+fn slots_reserve_their_size_hint() {
+    let long = "x".repeat(1000);
+
+    let m = html! { p { (long) } };
+
+    assert_eq!(m.as_inner().len(), 1007);
+    assert!(m.as_inner().capacity() >= 1007);
+}
+
+#[cfg(feature = "adaptive-capacity")]
+#[test]
+fn capacity_follows_previous_renders() {
+    fn render(items: &[u32]) -> Markup {
+        html! {
+            ul {
+                @for i in items {
+                    li { (i) }
+                }
+            }
+        }
+    }
+
+    let items = (0..100).collect::<Vec<_>>();
+    let first = render(&items);
+    let second = render(&items);
+
+    assert_eq!(first.as_inner(), second.as_inner());
+    assert!(second.as_inner().capacity() >= first.as_inner().len());
+}
+
+#[test]
+fn capacity_estimate_grows_at_once_and_decays_slowly() {
+    let capacity = Capacity::new(10);
+    assert_eq!(capacity.get(), 10);
+
+    capacity.update(1000);
+    assert_eq!(capacity.get(), 1000);
+
+    capacity.update(0);
+    assert_eq!(capacity.get(), 875);
+
+    for _ in 0..100 {
+        capacity.update(100);
+    }
+    assert!((100..108).contains(&capacity.get()));
+
+    for _ in 0..100 {
+        capacity.update(0);
+    }
+    assert!((10..18).contains(&capacity.get()));
+}
@@ -0,0 +1,122 @@
+use lfml::{component, html, Markup, Render};
+
+use crate::assert_html_eq;
+
+#[component]
+fn Card(title: &str, children: Markup) -> Markup {
+    html! {
+        div.card {
+            h2 { (title) }
+            (children)
+        }
+    }
+}
+
+#[component]
+fn Counter(count: i32, label: Option<&str>) -> Markup {
+    html! {
+        span { (label.unwrap_or("count")) ": " (count) }
+    }
+}
+
+mod ui {
+    use lfml::{component, html, Markup};
+
+    #[component]
+    pub fn Badge<T: lfml::Render>(value: T) -> Markup {
+        html! { b { (value) } }
+    }
+}
+
+#[component]
+struct Link<'a> {
+    href: &'a str,
+    children: Markup,
+}
+
+impl Render for Link<'_> {
+    fn markup_to_string(&self, buf: &mut String) {
+        html! { a href=(self.href) { (&self.children) } }.markup_to_string(buf);
+    }
+}
+
+#[test]
+fn function_component() {
+    assert_html_eq!({
+        Card title="<x>" {
+            p { "body" }
+        }
+    } => r#"<div class="card"><h2>&lt;x&gt;</h2><p>body</p></div>"#);
+}
+
+#[test]
+fn props_without_children() {
+    let n = 2;
+
+    assert_html_eq!({
+        Counter count=(n + 1) label=(None);
+        Counter count=-4 label=(Some("total"))
+    } => "<span>count: 3</span><span>total: -4</span>");
+}
+
+#[test]
+fn components_without_slots_take_only_their_props() {
+    let m = Counter(CounterProps {
+        count: 1,
+        label: Some("one"),
+    });
+
+    assert_eq!(m.as_inner(), "<span>one: 1</span>");
+    assert_eq!(
+        Link(LinkProps {
+            href: "/",
+            children: html! { "home" },
+        })
+        .markup()
+        .as_inner(),
+        r#"<a href="/">home</a>"#
+    );
+}
+
+#[test]
+fn component_paths_and_generics() {
+    assert_html_eq!({
+        ui::Badge value=1.5;
+        ui::Badge value="new"
+    } => "<b>1.5</b><b>new</b>");
+}
+
+#[test]
+fn struct_component() {
+    assert_html_eq!({
+        Link href="/a?b&c" { "link" }
+    } => r#"<a href="/a?b&amp;c">link</a>"#);
+}
+
+#[test]
+fn nested_components() {
+    let items = ["a", "b"];
+
+    assert_html_eq!({
+        Card title="list" {
+            @for i in items {
+                Card title=(i) {
+                    Counter count=1 label=(Some(i));
+                }
+            }
+        }
+    } => "<div class=\"card\"><h2>list</h2>\
+          <div class=\"card\"><h2>a</h2><span>a: 1</span></div>\
+          <div class=\"card\"><h2>b</h2><span>b: 1</span></div>\
+          </div>");
+}
+
+#[test]
+fn components_in_html_to() {
+    let mut s = String::new();
+    lfml::html_to!(&mut s, {
+        Card title="t" { "c" }
+    })
+    .unwrap();
+    assert_eq!(s, r#"<div class="card"><h2>t</h2>c</div>"#);
+}
@@ -1,2 +1,4 @@
 mod attrs;
+mod context;
 mod slots;
+mod types;
@@ -5,18 +5,18 @@ fn attrs() {
     let x = "bar";
 
     assert_html_eq!({
-        a foo=(x) {
+        a title=(x) {
             "Hello"
         }
-    } => "<a foo=\"bar\">Hello</a>");
+    } => "<a title=\"bar\">Hello</a>");
 
     let x = 3;
 
     assert_html_eq!({
-        a foo=(x) {
+        a title=(x) {
             "Hello"
         }
-    } => "<a foo=\"3\">Hello</a>");
+    } => "<a title=\"3\">Hello</a>");
 }
 
 #[test]
@@ -24,10 +24,10 @@ fn attrs_content_is_escaped() {
     let x = "<danger>";
 
     assert_html_eq!({
-        a foo=(x) {
+        a title=(x) {
             "Hello"
         }
-    } => "<a foo=\"&lt;danger&gt;\">Hello</a>");
+    } => "<a title=\"&lt;danger&gt;\">Hello</a>");
 }
 
 #[test]
@@ -35,15 +35,15 @@ fn attrs_without_values_can_be_toggled_with_boolean_valued_expressions() {
     let x = true;
 
     assert_html_eq!({
-        a foo[x] {
+        a hidden[x] {
             "Hello"
         }
-    } => "<a foo>Hello</a>");
+    } => "<a hidden>Hello</a>");
 
     let x = false;
 
     assert_html_eq!({
-        a foo[x] {
+        a hidden[x] {
             "Hello"
         }
     } => "<a>Hello</a>");
@@ -54,15 +54,15 @@ fn attrs_with_values_can_be_toggled_with_option_valued_expressions() {
     let x = Some(3);
 
     assert_html_eq!({
-        a foo=[x] {
+        a title=[x] {
             "Hello"
         }
-    } => "<a foo=\"3\">Hello</a>");
+    } => "<a title=\"3\">Hello</a>");
 
     let x: Option<i32> = None;
 
     assert_html_eq!({
-        a foo=[x] {
+        a title=[x] {
             "Hello"
         }
     } => "<a>Hello</a>");
@@ -73,8 +73,8 @@ fn attrs_can_be_literal_sequence() {
     let x = 3;
 
     assert_html_eq!({
-        a foo={ "name_" (x) } {
+        a title={ "name_" (x) } {
             "Hello"
         }
-    } => "<a foo=\"name_3\">Hello</a>");
+    } => "<a title=\"name_3\">Hello</a>");
 }
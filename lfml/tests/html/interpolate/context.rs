@@ -0,0 +1,136 @@
+use crate::assert_html_eq;
+
+#[test]
+fn url_attrs_reject_javascript() {
+    let x = "javascript:alert(1)";
+
+    assert_html_eq!({
+        a href=(x) { "A" }
+    } => "<a href=\"about:invalid#lfml-unsafe-url\">A</a>");
+
+    assert_html_eq!({
+        img src=[Some(x)];
+    } => "<img src=\"about:invalid#lfml-unsafe-url\">");
+
+    assert_html_eq!({
+        form action={ "javascript:" (x) } {}
+    } => "<form action=\"about:invalid#lfml-unsafe-url\"></form>");
+
+    let x = "data:text/html,<script>alert(1)</script>";
+
+    assert_html_eq!({
+        iframe src=(x) {}
+    } => "<iframe src=\"about:invalid#lfml-unsafe-url\"></iframe>");
+
+    let x = "data:image/png;base64,iVBORw0KGgo=";
+
+    assert_html_eq!({
+        img src=(x);
+    } => "<img src=\"data:image/png;base64,iVBORw0KGgo=\">");
+
+    let x = "/search?q=a&b=\"c\"";
+
+    assert_html_eq!({
+        a href=(x) { "A" }
+    } => "<a href=\"/search?q=a&amp;b=&quot;c&quot;\">A</a>");
+}
+
+#[test]
+fn optional_and_block_attrs_are_escaped() {
+    let x = "\"><script>";
+
+    assert_html_eq!({
+        a title=[Some(x)] {}
+    } => "<a title=\"&quot;&gt;&lt;script&gt;\"></a>");
+
+    assert_html_eq!({
+        a title={ "a&b " (x) } {}
+    } => "<a title=\"a&amp;b &quot;&gt;&lt;script&gt;\"></a>");
+}
+
+#[test]
+fn script_body() {
+    let json = "{\"name\":\"</script><script>alert(1)\"}";
+
+    assert_html_eq!({
+        script { "const x = 1 < 2 && JSON.parse(" (json) ");" }
+    } => "<script>const x = 1 < 2 && JSON.parse(\"{\\u0022name\\u0022:\\u0022\\u003c\\u002fscript\\u003e\\u003cscript\\u003ealert(1)\\u0022}\");</script>");
+}
+
+#[test]
+fn script_values_cant_end_their_string() {
+    let name = "\\';alert(1);//";
+
+    assert_html_eq!({
+        script { "var x = '" (name) "';" }
+    } => "<script>var x = '\"\\u005c\\u0027;alert(1);\\u002f\\u002f\"';</script>");
+
+    let name = "\\\";alert(1);//";
+
+    assert_html_eq!({
+        script { "var x = " (name) ";" }
+    } => "<script>var x = \"\\u005c\\u0022;alert(1);\\u002f\\u002f\";</script>");
+
+    assert_html_eq!({
+        script { "var n = " (1) ";" }
+    } => "<script>var n = \"1\";</script>");
+}
+
+#[test]
+fn style_body() {
+    let colour = "red}</style><script>";
+
+    assert_html_eq!({
+        style { "a > b { color: " (colour) " }" }
+    } => "<style>a > b { color: red\\7d \\3c /style\\3e \\3c script\\3e  }</style>");
+
+    let colour = "red} body{display:none";
+
+    assert_html_eq!({
+        style { "a { color: " (colour) " }" }
+    } => "<style>a { color: red\\7d  body\\7b display:none }</style>");
+
+    let font = "x', 'y";
+
+    assert_html_eq!({
+        style { "a { font-family: '" (font) "' }" }
+    } => "<style>a { font-family: 'x\\27 , \\27 y' }</style>");
+}
+
+#[test]
+fn single_quotes_are_escaped_in_text() {
+    let x = "it's";
+
+    assert_html_eq!({
+        p { (x) }
+    } => "<p>it&#39;s</p>");
+}
+
+#[test]
+fn values_are_escaped_as_they_are_formatted() {
+    struct Pieces(char);
+
+    impl std::fmt::Display for Pieces {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("a\"")?;
+            f.write_str("<")?;
+            write!(f, "{}>", self.0)
+        }
+    }
+
+    let expected = "<p title=\"a&quot;&lt;b&gt;\" class=\"a&quot;&lt;b&gt;\">\
+        <script>\"a\\u0022\\u003cb\\u003e\"</script></p>";
+
+    assert_eq!(
+        lfml::html! { p title=(Pieces('b')) .(Pieces('b')) { script { (Pieces('b')) } } }
+            .as_inner(),
+        expected
+    );
+
+    let mut s = String::new();
+    lfml::html_to!(s, {
+        p title=(Pieces('b')) .(Pieces('b')) { script { (Pieces('b')) } }
+    })
+    .unwrap();
+    assert_eq!(s, expected);
+}
@@ -142,3 +142,74 @@ fn slot_if() {
         }
     } => "<a>snake eyes!</a>");
 }
+
+#[test]
+fn slot_if_let_chain() {
+    enum Shape {
+        Circle { r: u32 },
+        Square(u32),
+        Dot,
+    }
+
+    let render = |s: Shape| {
+        lfml::html! {
+            @if let Shape::Circle { r } = s {
+                a { "circle " (r) }
+            } @else if let Shape::Square(w) = s {
+                a { "square " (w) }
+            } @else {
+                a { "dot" }
+            }
+        }
+        .into_inner()
+    };
+
+    assert_eq!(render(Shape::Circle { r: 2 }), "<a>circle 2</a>");
+    assert_eq!(render(Shape::Square(3)), "<a>square 3</a>");
+    assert_eq!(render(Shape::Dot), "<a>dot</a>");
+}
+
+#[test]
+fn slot_while() {
+    let mut n = 3;
+
+    assert_html_eq!({
+        @while n > 0 {
+            a { (n) }
+            @{ n -= 1; }
+        }
+    } => "<a>3</a><a>2</a><a>1</a>");
+
+    let mut stack = vec![1, 2];
+
+    assert_html_eq!({
+        @while let Some(i) = stack.pop() {
+            a { (i) }
+        }
+    } => "<a>2</a><a>1</a>");
+}
+
+#[test]
+fn slot_for_loop_context() {
+    let rows = ["a", "b", "c"];
+
+    assert_html_eq!({
+        table {
+            @for l, row in rows {
+                tr class=[l.is_odd().then_some("odd")] {
+                    td { (l.index) } td { (row) }
+                }
+            }
+        }
+    } => "<table><tr><td>0</td><td>a</td></tr>\
+          <tr class=\"odd\"><td>1</td><td>b</td></tr>\
+          <tr><td>2</td><td>c</td></tr></table>");
+
+    assert_html_eq!({
+        @for l, (i, c) in [(1, 'x'), (2, 'y')] {
+            @if l.first { "[" }
+            (i) (c)
+            @if l.last { "]" } @else { ", " }
+        }
+    } => "[1x, 2y]");
+}
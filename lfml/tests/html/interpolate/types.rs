@@ -0,0 +1,116 @@
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+use crate::assert_html_eq;
+
+#[test]
+fn floats() {
+    let x = 1.5f64;
+    let y = 0.1f32;
+    let z = 3.0f64;
+
+    assert_html_eq!({
+        span { (x) " " (y) " " (z) }
+    } => "<span>1.5 0.1 3.0</span>");
+
+    let nan = f64::NAN;
+    let inf = f64::NEG_INFINITY;
+
+    assert_html_eq!({
+        (nan) " " (inf)
+    } => "NaN -inf");
+}
+
+#[test]
+fn bools_and_chars() {
+    let t = true;
+    let f = false;
+    let c = '<';
+    let crab = '🦀';
+
+    assert_html_eq!({
+        (t) " " (f) " " (c) (crab)
+    } => "true false &lt;🦀");
+}
+
+#[test]
+fn options() {
+    let some = Some("<b>");
+    let none: Option<&str> = None;
+
+    assert_html_eq!({
+        a { (some) }
+        a { (none) }
+    } => "<a>&lt;b&gt;</a><a></a>");
+}
+
+#[test]
+fn smart_pointers() {
+    let boxed = Box::new(1);
+    let rc = Rc::new("<rc>".to_string());
+    let arc: Arc<str> = Arc::from("&arc");
+    let cow: Cow<str> = Cow::Borrowed("cow>");
+
+    assert_html_eq!({
+        (boxed) (rc) (arc) (cow)
+    } => "1&lt;rc&gt;&amp;arccow&gt;");
+}
+
+#[test]
+fn render_to_matches_markup() {
+    use lfml::Render;
+
+    let mut s = String::new();
+    Some(2.5f64).render_to(&mut s).unwrap();
+    '&'.render_to(&mut s).unwrap();
+    assert_eq!(s, "2.5&amp;");
+}
+
+#[test]
+fn collections() {
+    let items = vec![lfml::html! { li { "a" } }, lfml::html! { li { "b" } }];
+    let words = ["<", ">"];
+    let nums: &[i32] = &[1, 2, 3];
+
+    assert_html_eq!({
+        ul { (items) }
+        (words) (nums)
+    } => "<ul><li>a</li><li>b</li></ul>&lt;&gt;123");
+}
+
+#[test]
+fn join() {
+    let names = ["a", "b&", "c"];
+
+    assert_html_eq!({
+        (lfml::join(names, ", "))
+    } => "a, b&amp;, c");
+
+    let items = names.iter().map(|n| lfml::html! { b { (n) } });
+    let separator = lfml::html! { br; };
+
+    assert_html_eq!({
+        p { (lfml::join(items, separator)) }
+    } => "<p><b>a</b><br><b>b&amp;</b><br><b>c</b></p>");
+
+    let empty: [&str; 0] = [];
+
+    assert_html_eq!({
+        (lfml::join(empty, ", "))
+    } => "");
+}
+
+#[test]
+fn raw() {
+    let icon = lfml::raw("<svg></svg>");
+    let count = lfml::PreEscaped(3);
+
+    assert_html_eq!({
+        button { (&icon) (count) "<" }
+    } => "<button><svg></svg>3&lt;</button>");
+
+    let mut s = String::new();
+    lfml::Render::render_to(&icon, &mut s).unwrap();
+    assert_eq!(s, "<svg></svg>");
+
+    assert_eq!(lfml::html! { br; }.into_inner(), "<br>");
+}
@@ -0,0 +1,160 @@
+use lfml::{component, html, Markup, Render, Slot};
+
+use crate::assert_html_eq;
+
+#[component]
+fn Base(title: &str, head: Slot, content: Slot) -> Markup {
+    html! {
+        html {
+            head {
+                title { (title) }
+                @slot head {
+                    meta charset="utf-8";
+                }
+            }
+            body {
+                main { @slot content; }
+            }
+        }
+    }
+}
+
+#[test]
+fn filled_slots() {
+    let heading = "<Home>";
+
+    assert_html_eq!({
+        Base title="Home" {
+            @slot head {
+                link rel="stylesheet" href="/a.css";
+            }
+            @slot content {
+                h1 { (heading) }
+            }
+        }
+    } => "<html><head><title>Home</title><link rel=\"stylesheet\" href=\"/a.css\"></head>\
+          <body><main><h1>&lt;Home&gt;</h1></main></body></html>");
+}
+
+#[test]
+fn unfilled_slots_use_defaults() {
+    assert_html_eq!({
+        Base title="Empty" {
+            @slot content { "x" }
+        }
+    } => "<html><head><title>Empty</title><meta charset=\"utf-8\"></head>\
+          <body><main>x</main></body></html>");
+}
+
+#[test]
+fn empty_fill_overrides_default() {
+    assert_html_eq!({
+        Base title="t" {
+            @slot head;
+            @slot content;
+        }
+    } => "<html><head><title>t</title></head><body><main></main></body></html>");
+}
+
+#[component]
+fn Section<'a>(id: &'a str, aside: Slot<'a>, children: Markup) -> Markup {
+    html! {
+        section #(id) {
+            (children)
+            @slot aside { "no aside" }
+        }
+    }
+}
+
+#[test]
+fn slots_in_nested_layouts() {
+    let items = ["a", "b"];
+
+    assert_html_eq!({
+        Base title="nested" {
+            @slot content {
+                @for i in items {
+                    Section id=(i) { p { (i) } }
+                }
+            }
+        }
+    } => "<html><head><title>nested</title><meta charset=\"utf-8\"></head><body><main>\
+          <section id=\"a\"><p>a</p>no aside</section>\
+          <section id=\"b\"><p>b</p>no aside</section>\
+          </main></body></html>");
+}
+
+#[test]
+fn slots_in_html_to() {
+    let mut s = String::new();
+    lfml::html_to!(&mut s, {
+        Base title="t" {
+            @slot content { "c" }
+        }
+    })
+    .unwrap();
+    assert_eq!(
+        s,
+        "<html><head><title>t</title><meta charset=\"utf-8\"></head><body><main>c</main></body></html>"
+    );
+}
+
+#[test]
+fn let_bindings_between_fills() {
+    assert_html_eq!({
+        Base title="t" {
+            @let heading = "computed".to_uppercase();
+            @slot content { h1 { (&heading) } }
+        }
+    } => "<html><head><title>t</title><meta charset=\"utf-8\"></head>\
+          <body><main><h1>COMPUTED</h1></main></body></html>");
+}
+
+#[test]
+fn layouts_take_their_slots_after_their_props() {
+    let content = |w: &mut dyn core::fmt::Write| w.write_str("<p>hi</p>");
+    let m = Base(
+        BaseProps { title: "Home" },
+        BaseSlots {
+            content: Slot::new(&content),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        m.as_inner(),
+        "<html><head><title>Home</title><meta charset=\"utf-8\"></head>\
+         <body><main><p>hi</p></main></body></html>"
+    );
+}
+
+mod icons {
+    /// Not a layout slot, just named like one.
+    pub struct Slot(pub &'static str);
+
+    impl lfml::Render for Slot {
+        fn markup_to_string(&self, buf: &mut String) {
+            lfml::html! { i.(self.0) {} }.markup_to_string(buf);
+        }
+    }
+}
+
+#[component]
+fn Button(icon: icons::Slot) -> Markup {
+    html! { button { (icon) } }
+}
+
+#[test]
+fn other_types_named_slot_are_props() {
+    assert_html_eq!({
+        Button icon=(icons::Slot("save"));
+    } => r#"<button><i class="save"></i></button>"#);
+    assert_eq!(
+        Button(ButtonProps {
+            icon: icons::Slot("x")
+        })
+        .markup()
+        .as_inner(),
+        r#"<button><i class="x"></i></button>"#
+    );
+}
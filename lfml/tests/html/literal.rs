@@ -19,6 +19,11 @@ fn not_string_that_impl_display() {
     assert_html_eq!({ true " " false } => "true false");
 }
 
+#[test]
+fn integers_render_as_their_value() {
+    assert_html_eq!({ 007 " " 0x1f " " 1_000u32 } => "7 31 1000");
+}
+
 #[test]
 fn negative_numbers() {
     assert_html_eq!({ -1 } => "-1");
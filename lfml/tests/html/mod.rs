@@ -1,6 +1,12 @@
 mod attrs;
 mod block;
+mod capacity;
+mod component;
 mod interpolate;
+mod layout;
 mod literal;
 mod shorthand;
 mod spread;
+mod statements;
+mod style;
+mod write_to;
@@ -121,8 +121,69 @@ fn id_ident() {
 }
 
 #[test]
+#[cfg(not(feature = "strict"))]
 fn class_shorthand_after_valueless_tag() {
     assert_html_eq!({
         a foo-bar #headers {}
     } => "<a foo-bar id=\"headers\"></a>");
 }
+
+#[test]
+fn class_shorthands_are_merged() {
+    assert_html_eq!({
+        a.foo.bar {}
+    } => "<a class=\"foo bar\"></a>");
+
+    assert_html_eq!({
+        a #x .foo href="/" ."bar" .(1 + 1) {}
+    } => "<a id=\"x\" class=\"foo bar 2\" href=\"/\"></a>");
+
+    assert_html_eq!({
+        a.foo.[Some("on")].[None::<&str>].("<b>") {}
+    } => "<a class=\"foo on &lt;b&gt;\"></a>");
+}
+
+#[test]
+fn optional_class_shorthands_are_merged() {
+    fn render(a: Option<&str>, b: Option<&str>) -> lfml::Markup {
+        lfml::html! { p.[a].[b] {} }
+    }
+
+    assert_eq!(render(None, None).as_inner(), "<p></p>");
+    assert_eq!(render(Some("a"), None).as_inner(), "<p class=\"a\"></p>");
+    assert_eq!(render(None, Some("b")).as_inner(), "<p class=\"b\"></p>");
+    assert_eq!(
+        render(Some("a"), Some("b")).as_inner(),
+        "<p class=\"a b\"></p>"
+    );
+
+    fn render_around(a: Option<&str>) -> lfml::Markup {
+        lfml::html! { p.[a].foo.[a] {} }
+    }
+
+    assert_eq!(render_around(None).as_inner(), "<p class=\"foo\"></p>");
+    assert_eq!(
+        render_around(Some("a")).as_inner(),
+        "<p class=\"a foo a\"></p>"
+    );
+}
+
+#[test]
+fn classes_macro() {
+    let active = true;
+    let extra = String::from("x y");
+
+    assert_html_eq!({
+        a.(lfml::classes! { "btn", "active" => active, "hidden" => !active, extra, "btn" }) {}
+    } => "<a class=\"btn active x y\"></a>");
+
+    let classes = lfml::classes! { "b" => false };
+    assert!(classes.is_empty());
+    assert_eq!(
+        ["a", "b", "a"]
+            .into_iter()
+            .collect::<lfml::Classes>()
+            .to_string(),
+        "a b"
+    );
+}
@@ -1,2 +1,3 @@
+mod conflicts;
 mod r#enum;
 mod r#struct;
@@ -0,0 +1,82 @@
+use crate::assert_html_eq;
+
+use lfml::Spread;
+
+#[derive(Spread)]
+struct Attrs<'a> {
+    class: &'a str,
+    style: &'a str,
+    title: &'a str,
+}
+
+#[test]
+fn no_conflicts() {
+    #[derive(Spread)]
+    struct Data<'a> {
+        title: &'a str,
+    }
+
+    let x = Data { title: "t" };
+
+    assert_html_eq!({
+        a href="/" @(x) hidden { "A" }
+    } => "<a href=\"/\" title=\"t\" hidden>A</a>");
+}
+
+#[test]
+fn merge_by_default() {
+    let x = Attrs {
+        class: "b",
+        style: "color: red;",
+        title: "from spread",
+    };
+
+    assert_html_eq!({
+        a.a style="margin: 0;" title=("first") @(x) {}
+    } => "<a class=\"a b\" style=\"margin: 0; color: red;\" title=\"from spread\"></a>");
+
+    assert_html_eq!({
+        a @(x) title="last" .(1) @conflicts(merge) {}
+    } => "<a class=\"b 1\" style=\"color: red;\" title=\"last\"></a>");
+}
+
+#[test]
+fn last_wins() {
+    let x = Attrs {
+        class: "b",
+        style: "color: red",
+        title: "t",
+    };
+
+    assert_html_eq!({
+        a.a.[Some("c")] style="margin: 0" @(x) @conflicts(last_wins) {}
+    } => "<a class=\"b\" style=\"color: red\" title=\"t\"></a>");
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic = "the attribute `title` is set twice")]
+fn error() {
+    let x = Attrs {
+        class: "b",
+        style: "",
+        title: "t",
+    };
+
+    assert_html_eq!({
+        a title="a" @(x) @conflicts(error) {}
+    } => "<a title=\"a\" class=\"b\" style=\"\"></a>");
+}
+
+#[test]
+fn optional_attributes_only_conflict_when_set() {
+    let x = Attrs {
+        class: "b",
+        style: "s",
+        title: "t",
+    };
+    let title = None::<&str>;
+
+    assert_html_eq!({
+        a title=[title] hidden[false] .[None::<&str>] @(x) @conflicts(error) {}
+    } => "<a class=\"b\" style=\"s\" title=\"t\"></a>");
+}
@@ -50,6 +50,7 @@ fn restrict_attribute() {
         b@(HxControl::Get { get: "/a", target: ".main", swap: "outerHTML" }) { "A" }
         b@(HxControl::Post { post: "/a", target: ".main", swap: "outerHTML" }) { "A" }
     } => "<b hx-get=\"/a\" hx-target=\".main\" hx-swap=\"outerHTML\">A</b><b hx-post=\"/a\" hx-target=\".main\" hx-swap=\"outerHTML\">A</b>");
+    #[cfg(not(feature = "strict"))]
     assert_html_eq!({
         c@(HxControl::Get { get: "/a", target: ".main", swap: "outerHTML" }) { "A" }
         c@(HxControl::Post { post: "/a", target: ".main", swap: "outerHTML" }) { "A" }
@@ -100,7 +101,7 @@ fn prefix_precedent() {
             "A"
         }
         a @(Bar::Bat { get: "/" });
-    } => "<a data-get=\"/\">A</a><a x-data-get=\"/\">");
+    } => "<a data-get=\"/\">A</a><a x-data-get=\"/\"></a>");
 }
 
 #[test]
@@ -127,3 +128,32 @@ fn option_type_with_toggle_syntax() {
         }
     } => "<a>A</a>");
 }
+
+#[test]
+fn unit_and_newtype_variants() {
+    #[derive(Spread)]
+    struct Get<'a> {
+        #[rename = "hx-get"]
+        url: &'a str,
+    }
+
+    #[derive(Spread)]
+    #[tags(only(button))]
+    enum Control<'a> {
+        #[spread(attrs(r#type = "submit"))]
+        Submit,
+        Fetch(Get<'a>),
+    }
+
+    let x = Control::Submit;
+
+    assert_html_eq!({
+        button @(x) { "A" }
+    } => "<button type=\"submit\">A</button>");
+
+    let x = Control::Fetch(Get { url: "/more" });
+
+    assert_html_eq!({
+        button.more @(x) { "B" }
+    } => "<button class=\"more\" hx-get=\"/more\">B</button>");
+}
@@ -68,6 +68,7 @@ fn restrict_attribute() {
         }
     } => "<b hx-get=\"/a\" hx-target=\".main\" hx-swap=\"outerHTML\">A</b>");
 
+    #[cfg(not(feature = "strict"))]
     assert_html_eq!({
         c@(x) { "A" }
     } => "<c hx-get=\"/a\" hx-target=\".main\" hx-swap=\"outerHTML\">A</c>");
@@ -120,3 +121,60 @@ fn option_type_with_toggle_syntax() {
         }
     } => "<a>A</a>");
 }
+
+#[test]
+fn url_attrs_reject_javascript() {
+    #[derive(Spread)]
+    struct Link<'a> {
+        href: &'a str,
+        #[rename = "SRC"]
+        src: &'a str,
+        title: &'a str,
+    }
+
+    let x = Link {
+        href: "javascript:alert(1)",
+        src: "java&#115;cript:alert(1)",
+        title: "javascript:",
+    };
+
+    assert_html_eq!({
+        a @(x) {}
+    } => "<a href=\"about:invalid#lfml-unsafe-url\" SRC=\"about:invalid#lfml-unsafe-url\" title=\"javascript:\"></a>");
+
+    assert_eq!(
+        x.raw(),
+        " href=\"about:invalid#lfml-unsafe-url\" SRC=\"about:invalid#lfml-unsafe-url\" title=\"javascript:\""
+    );
+}
+
+#[test]
+fn spreads_in_html_to() {
+    #[derive(Spread)]
+    struct Link<'a> {
+        href: &'a str,
+        class: &'a str,
+    }
+
+    let x = Link {
+        href: "javascript:alert(1)",
+        class: "b",
+    };
+    let y = Link {
+        href: "/b?c=1&d=2",
+        class: "c",
+    };
+
+    let mut s = String::new();
+    lfml::html_to!(s, {
+        a @(x) {}
+        a.a @(y) {}
+    })
+    .unwrap();
+
+    assert_eq!(
+        s,
+        "<a href=\"about:invalid#lfml-unsafe-url\" class=\"b\"></a>\
+         <a class=\"a c\" href=\"/b?c=1&d=2\"></a>"
+    );
+}
@@ -0,0 +1,82 @@
+use crate::assert_html_eq;
+
+#[test]
+fn let_binding() {
+    let name = "world";
+
+    assert_html_eq!({
+        @let greeting = format!("hello {name}");
+        p { (greeting) }
+    } => "<p>hello world</p>");
+}
+
+#[test]
+fn let_with_pattern_and_block_expression() {
+    let pair = (1, Some(2));
+
+    assert_html_eq!({
+        @let (a, b) = pair;
+        @let b = match b {
+            Some(b) => { let b = b * 2; b + 1 }
+            None => 0,
+        };
+        (a) " " (b)
+    } => "1 5");
+}
+
+#[test]
+fn let_is_scoped_to_its_tag() {
+    let x = "outer";
+
+    assert_html_eq!({
+        div {
+            @let x = "inner";
+            (x)
+        }
+        (x)
+        {
+            @let x = "anon";
+            (x)
+        }
+        (x)
+    } => "<div>inner</div>outeranonouter");
+}
+
+#[test]
+fn let_in_loops() {
+    let items = [1, 2, 3];
+
+    assert_html_eq!({
+        ul {
+            @for i in items {
+                @let square = i * i;
+                li { (square) }
+            }
+        }
+    } => "<ul><li>1</li><li>4</li><li>9</li></ul>");
+}
+
+#[test]
+fn statement_block() {
+    let items = ["b", "a", "c"];
+
+    assert_html_eq!({
+        @{
+            let mut sorted = items.to_vec();
+            sorted.sort();
+            let joined = sorted.join(",");
+        }
+        span { (joined) }
+    } => "<span>a,b,c</span>");
+}
+
+#[test]
+fn statements_in_html_to() {
+    let mut s = String::new();
+    lfml::html_to!(&mut s, {
+        @let n = 2 + 2;
+        b { (n) }
+    })
+    .unwrap();
+    assert_eq!(s, "<b>4</b>");
+}
@@ -0,0 +1,84 @@
+use crate::assert_html_eq;
+
+use lfml::Style;
+
+#[test]
+fn literal_declarations() {
+    assert_html_eq!({
+        p style { color: "red"; margin: "0 auto"; z-index: 2; --main-color: "blue" } {}
+    } => "<p style=\"color: red; margin: 0 auto; z-index: 2; --main-color: blue\"></p>");
+
+    assert_html_eq!({
+        p.a style { margin: -1 } { "b" }
+    } => "<p class=\"a\" style=\"margin: -1\">b</p>");
+}
+
+#[test]
+fn interpolated_declarations() {
+    let (color, size) = ("red", 12);
+
+    assert_html_eq!({
+        p style { color: (color); font-size: (format!("{size}px")); margin: "0" } {}
+    } => "<p style=\"color: red; font-size: 12px; margin: 0\"></p>");
+}
+
+#[test]
+fn none_declarations_are_left_out() {
+    let (some, none) = (Some("red"), None::<&str>);
+
+    assert_html_eq!({
+        p style { color: [some]; width: [none]; margin: "0" } {}
+    } => "<p style=\"color: red; margin: 0\"></p>");
+
+    assert_html_eq!({
+        p style { width: [none]; height: [none] } {}
+    } => "<p></p>");
+}
+
+#[test]
+fn values_cant_end_their_declaration() {
+    let color = "red; position: fixed";
+
+    assert_html_eq!({
+        p style { color: (color) } {}
+    } => "<p style=\"color: red\\3b  position: fixed\"></p>");
+
+    assert_html_eq!({
+        p style { content: "\"}</style>" } {}
+    } => "<p style=\"content: \\22 \\7d \\3c /style\\3e \"></p>");
+}
+
+#[test]
+fn style_block_without_declarations_is_content() {
+    assert_html_eq!({
+        p style { "content" }
+    } => "<p style>content</p>");
+}
+
+#[test]
+fn style_with_spread() {
+    #[derive(lfml::Spread)]
+    struct Data<'a> {
+        style: &'a str,
+    }
+
+    let x = Data {
+        style: "color: red;",
+    };
+
+    assert_html_eq!({
+        p style { margin: "0" } @(x) {}
+    } => "<p style=\"margin: 0; color: red;\"></p>");
+}
+
+#[test]
+fn style_builder() {
+    let mut style = Style::new();
+    style.push("color", "red");
+    style.push_option("width", None::<&str>);
+    style.push("margin", 0);
+    style.push("color", "blue;");
+
+    assert_eq!(style.to_string(), "color: blue\\3b ; margin: 0");
+    assert!(Style::new().is_empty());
+}
@@ -0,0 +1,99 @@
+use lfml::{html, html_to, IoWriter, Render};
+
+#[test]
+fn write_to_string() {
+    let mut s = String::from("<!-- head -->");
+    let x = "<b>";
+
+    html_to!(s, {
+        a title=(x) {
+            "Hello " (x)
+        }
+    })
+    .unwrap();
+
+    assert_eq!(s, "<!-- head --><a title=\"&lt;b&gt;\">Hello &lt;b&gt;</a>");
+}
+
+#[test]
+fn write_to_mutable_reference() {
+    let mut s = String::new();
+
+    html_to!(&mut s, {
+        @for i in 0..3 {
+            a { (i) }
+        }
+    })
+    .unwrap();
+
+    assert_eq!(s, "<a>0</a><a>1</a><a>2</a>");
+}
+
+#[test]
+fn write_to_io() {
+    let mut w = IoWriter::new(Vec::new());
+
+    html_to!(w, { p { "x < y" } }).unwrap();
+
+    assert_eq!(w.into_inner(), b"<p>x &lt; y</p>");
+}
+
+#[test]
+fn write_errors_are_returned() {
+    struct Full;
+
+    impl std::io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::WriteZero.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut w = IoWriter::new(Full);
+
+    assert!(html_to!(w, { p { "unwritten" } }).is_err());
+    assert_eq!(
+        w.take_error().unwrap_err().kind(),
+        std::io::ErrorKind::WriteZero
+    );
+}
+
+#[test]
+fn render_to_matches_markup() {
+    let x = html! { a { "Foo" (3) } };
+
+    let mut s = String::new();
+    x.render_to(&mut s).unwrap();
+    "&".render_to(&mut s).unwrap();
+
+    assert_eq!(s, "<a>Foo3</a>&amp;");
+}
+
+#[test]
+fn write_merged_classes() {
+    let mut s = String::new();
+    let active = Some("active");
+
+    html_to!(s, { a.[None::<&str>].btn.[active] {} }).unwrap();
+
+    assert_eq!(s, "<a class=\"btn active\"></a>");
+}
+
+#[test]
+fn write_spread() {
+    #[derive(lfml::Spread)]
+    struct Link<'a> {
+        href: &'a str,
+    }
+
+    let mut s = String::new();
+    let link = Link { href: "/a" };
+    let none = None::<Link>;
+
+    html_to!(s, { a @(link) @[none] {} }).unwrap();
+
+    assert_eq!(s, "<a href=\"/a\"></a>");
+}
@@ -1,4 +1,6 @@
 mod html;
+mod pretty;
+mod sanitize;
 mod spread;
 
 #[macro_export]
@@ -7,3 +9,9 @@ macro_rules! assert_html_eq {
         assert_eq!({ lfml::html! $markup }.as_string(), $output)
     };
 }
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
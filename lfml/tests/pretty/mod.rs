@@ -0,0 +1,106 @@
+use lfml::{html, pretty, raw, DOCTYPE};
+
+#[test]
+fn pretty_page() {
+    let page = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { "Home" }
+                script { "let x = 1 < 2;" }
+            }
+            body {
+                h1 { "Welcome" }
+                div.content {
+                    p { "Some " strong { "bold" } " text." }
+                    "Loose text, " a href="/" { "a link" }
+                    hr;
+                }
+            }
+        }
+    };
+
+    assert_eq!(
+        pretty(&page).as_inner(),
+        "<!doctype html>
+<html lang=\"en\">
+  <head>
+    <meta charset=\"utf-8\">
+    <title>Home</title>
+    <script>let x = 1 < 2;</script>
+  </head>
+  <body>
+    <h1>Welcome</h1>
+    <div class=\"content\">
+      <p>Some <strong>bold</strong> text.</p>
+      Loose text, <a href=\"/\">a link</a>
+      <hr>
+    </div>
+  </body>
+</html>"
+    );
+}
+
+#[test]
+fn pretty_keeps_verbatim_content() {
+    let page = html! {
+        div {
+            pre { "line 1\n  " b { "line 2" } "\n" }
+            textarea { "a\n <p>b</p>" }
+        }
+    };
+
+    assert_eq!(
+        pretty(&page).as_inner(),
+        "<div>
+  <pre>line 1
+  <b>line 2</b>
+</pre>
+  <textarea>a
+ &lt;p&gt;b&lt;/p&gt;</textarea>
+</div>"
+    );
+}
+
+#[test]
+fn pretty_inline_elements_with_blocks() {
+    let page = html! {
+        a href="/" { div { "card" } }
+    };
+
+    assert_eq!(
+        pretty(&page).as_inner(),
+        "<a href=\"/\">
+  <div>card</div>
+</a>"
+    );
+}
+
+#[test]
+fn pretty_unbalanced_tags() {
+    assert_eq!(
+        pretty(&raw("<div><p>a</div></span>é")).as_inner(),
+        "<div>\n  <p>a\n</div>\n</span>é"
+    );
+    assert_eq!(
+        pretty(&raw("<ul><li>unclosed")).as_inner(),
+        "<ul>\n  <li>unclosed"
+    );
+}
+
+#[test]
+fn pretty_keeps_whitespace_between_inline_content() {
+    assert_eq!(
+        pretty(&raw(
+            "<div><p>x</p><span>a</span> <span>b</span>\n<em>c</em> \n <div>d</div></div>"
+        ))
+        .as_inner(),
+        "<div>\n  <p>x</p>\n  <span>a</span> <span>b</span> <em>c</em>\n  <div>d</div>\n</div>"
+    );
+}
+
+#[test]
+fn pretty_escapes_text() {
+    assert_eq!(pretty("<p>a</p>").as_inner(), "&lt;p&gt;a&lt;/p&gt;");
+}
@@ -0,0 +1,88 @@
+use lfml::{escape::sanitize::Sanitizer, sanitize, sanitize_with};
+
+#[test]
+fn keeps_allowed_markup() {
+    let html = r#"<p class="lead">Some <em>rich</em> <a href="https://example.com" title="x">text</a><br>&amp; more</p>"#;
+
+    assert_eq!(sanitize(html).as_inner(), html);
+}
+
+#[test]
+fn drops_scripts_and_handlers() {
+    assert_eq!(
+        sanitize(r#"<script>alert("<b>")</script><img src="/a.png" onerror="alert(1)" alt=a>"#)
+            .as_inner(),
+        r#"<img src="/a.png" alt="a">"#
+    );
+
+    assert_eq!(
+        sanitize("<STYLE>body{}</style><svg><b>x</b></svg><!-- c -->ok").as_inner(),
+        "ok"
+    );
+}
+
+#[test]
+fn keeps_the_content_of_denied_tags() {
+    assert_eq!(
+        sanitize(r#"<form action="/x"><b>bold</b><input value="v"></form>"#).as_inner(),
+        "<b>bold</b>"
+    );
+}
+
+#[test]
+fn drops_unsafe_urls() {
+    for url in [
+        "javascript:alert(1)",
+        " JaVaScRiPt:alert(1)",
+        "java\tscript:alert(1)",
+        "&#106;avascript:alert(1)",
+        "javascript&colon;alert(1)",
+        "data:text/html,<script>alert(1)</script>",
+    ] {
+        let html = format!(r#"<a href="{url}">x</a>"#);
+        assert_eq!(sanitize(&html).as_inner(), "<a>x</a>", "{url}");
+    }
+
+    assert_eq!(
+        sanitize(r#"<a href="/a:b">x</a><a href=mailto:a@b.c>y</a>"#).as_inner(),
+        r#"<a href="/a:b">x</a><a href="mailto:a@b.c">y</a>"#
+    );
+}
+
+#[test]
+fn output_is_well_formed() {
+    assert_eq!(
+        sanitize("<div><b><i>a</b> 1 < 2 >").as_inner(),
+        "<div><b><i>a</i></b> 1 &lt; 2 &gt;</div>"
+    );
+
+    assert_eq!(
+        sanitize("</div><b title='\"'>x").as_inner(),
+        "<b title=\"&quot;\">x</b>"
+    );
+
+    assert_eq!(
+        sanitize(r#"<B TITLE="a" title="b" ID="c">x</B>"#).as_inner(),
+        r#"<b title="a">x</b>"#
+    );
+}
+
+#[test]
+fn custom_allowlist() {
+    let sanitizer = Sanitizer::new()
+        .deny_tag("a")
+        .allow_tag("my-tag")
+        .allow_attribute("my-tag", "data-x")
+        .deny_attribute("*", "title")
+        .allow_url_scheme("ftp")
+        .allow_tag("script");
+
+    assert_eq!(
+        sanitize_with(
+            &sanitizer,
+            r#"<a href="/">a</a><my-tag data-x="1" title="t">b</my-tag><img src="ftp://x"><script>c</script>"#
+        )
+        .as_inner(),
+        r#"a<my-tag data-x="1">b</my-tag><img src="ftp://x">"#
+    );
+}
@@ -15,3 +15,135 @@ fn basic() {
 
     assert_eq!(lfml::Spread::raw(&y), " b=\"a\"");
 }
+
+#[test]
+fn unit_and_tuple_variants() {
+    #[derive(lfml::Spread)]
+    struct Target<'a> {
+        href: &'a str,
+    }
+
+    #[derive(lfml::Spread)]
+    enum Action<'a> {
+        #[spread(attrs(r#type = "submit", formnovalidate))]
+        Submit,
+        None,
+        Link(Target<'a>),
+        #[spread(attrs(download))]
+        Download(Target<'a>),
+        Named {
+            title: &'a str,
+        },
+    }
+
+    assert_eq!(
+        lfml::Spread::raw(&Action::Submit),
+        " type=\"submit\" formnovalidate"
+    );
+    assert_eq!(lfml::Spread::raw(&Action::None), "");
+    assert_eq!(
+        lfml::Spread::raw(&Action::Link(Target { href: "/a" })),
+        " href=\"/a\""
+    );
+    assert_eq!(
+        lfml::Spread::raw(&Action::Download(Target { href: "/a" })),
+        " download href=\"/a\""
+    );
+    assert_eq!(
+        lfml::Spread::raw(&Action::Named { title: "t" }),
+        " title=\"t\""
+    );
+}
+
+#[test]
+fn variant_field_options() {
+    #[derive(lfml::Spread)]
+    enum Input<'a> {
+        Text {
+            #[spread(skip)]
+            _id: u32,
+            #[spread(bool)]
+            required: bool,
+            #[spread(skip_if = "str::is_empty")]
+            placeholder: &'a str,
+        },
+    }
+
+    let y = Input::Text {
+        _id: 1,
+        required: true,
+        placeholder: "",
+    };
+
+    assert_eq!(lfml::Spread::raw(&y), " required");
+}
+
+#[test]
+fn rename_all() {
+    #[derive(lfml::Spread)]
+    #[spread(rename_all = "kebab-case")]
+    enum Link<'a> {
+        Anchor {
+            aria_label: &'a str,
+        },
+        #[spread(rename_all = "UPPERCASE")]
+        Button {
+            aria_label: &'a str,
+            r#type: &'a str,
+        },
+    }
+
+    let y = Link::Anchor { aria_label: "Home" };
+    assert_eq!(lfml::Spread::raw(&y), " aria-label=\"Home\"");
+
+    let y = Link::Button {
+        aria_label: "Home",
+        r#type: "submit",
+    };
+    assert_eq!(
+        lfml::Spread::raw(&y),
+        " ARIA_LABEL=\"Home\" TYPE=\"submit\""
+    );
+
+    #[derive(lfml::Spread)]
+    #[spread(rename_all = "lowercase")]
+    struct Lower {
+        aria_label: bool,
+    }
+
+    assert_eq!(
+        lfml::Spread::raw(&Lower { aria_label: true }),
+        " aria_label=\"true\""
+    );
+}
+
+#[test]
+fn generic_variants() {
+    #[derive(lfml::Spread)]
+    struct Label<'a> {
+        aria_label: &'a str,
+    }
+
+    #[derive(lfml::Spread)]
+    enum Field<T, U> {
+        Text {
+            value: T,
+        },
+        Labelled(U),
+        #[spread(attrs(hidden))]
+        Hidden {
+            _marker: std::marker::PhantomData<T>,
+        },
+    }
+
+    let y = Field::<_, Label>::Text { value: 1 };
+    assert_eq!(lfml::Spread::raw(&y), " value=\"1\"");
+
+    let y = Field::<u8, _>::Labelled(Label { aria_label: "x" });
+    assert_eq!(lfml::Spread::raw(&y), " aria_label=\"x\"");
+
+    let y = Field::<u8, Label>::Hidden {
+        _marker: std::marker::PhantomData,
+    };
+    assert_eq!(lfml::Spread::raw(&y), " hidden");
+}
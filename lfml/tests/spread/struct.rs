@@ -190,6 +190,70 @@ fn display_trait_bound_is_delegated_for_generic_types() {
     let _: A<()> = A { foo: () };
 }
 
+#[test]
+fn bounds_are_inferred_from_field_usage() {
+    use std::{fmt::Display, marker::PhantomData};
+
+    struct NotDisplay;
+
+    fn len<T>(v: &[T]) -> usize {
+        v.len()
+    }
+
+    #[derive(Spread)]
+    struct A<T, U, V>
+    where
+        T: Copy,
+    {
+        title: T,
+        #[spread(skip)]
+        _hidden: U,
+        _marker: PhantomData<V>,
+    }
+
+    #[derive(Spread)]
+    struct B<'a, U> {
+        #[spread(with = "len")]
+        count: &'a [U],
+    }
+
+    #[derive(Spread)]
+    #[spread(bound = "T: Display")]
+    struct C<T> {
+        #[escape_value]
+        value: Option<T>,
+    }
+
+    #[derive(Spread)]
+    struct D<T, U> {
+        #[spread(flatten)]
+        inner: T,
+        #[spread(bound = "U: AsRef<str>", with = "AsRef::as_ref")]
+        name: U,
+    }
+
+    let y = A {
+        title: 1,
+        _hidden: NotDisplay,
+        _marker: PhantomData::<NotDisplay>,
+    };
+    assert_eq!(Spread::raw(&y), " title=\"1\"");
+
+    let y = B {
+        count: &[NotDisplay, NotDisplay],
+    };
+    assert_eq!(Spread::raw(&y), " count=\"2\"");
+
+    let y = C { value: Some("<") };
+    assert_eq!(Spread::raw(&y), " value=\"&lt;\"");
+
+    let y = D {
+        inner: B { count: &[1] },
+        name: String::from("x"),
+    };
+    assert_eq!(Spread::raw(&y), " count=\"1\" name=\"x\"");
+}
+
 #[test]
 fn option_fields_are_handled() {
     #[derive(Spread)]
@@ -285,3 +349,219 @@ fn spread_with_option_unit_nameonly_field_produces_valueless_tag() {
 
     assert_eq!(Spread::raw(&y), "");
 }
+
+#[test]
+fn flatten() {
+    #[derive(Spread)]
+    struct Common<'a> {
+        id: &'a str,
+        class: &'a str,
+    }
+
+    #[derive(Spread)]
+    #[prefix = "hx"]
+    struct Link<'a> {
+        get: &'a str,
+        #[spread(flatten)]
+        common: Common<'a>,
+        #[spread(flatten)]
+        extra: Option<Common<'a>>,
+    }
+
+    let y = Link {
+        get: "/a",
+        common: Common {
+            id: "x",
+            class: "y",
+        },
+        extra: None,
+    };
+
+    assert_eq!(Spread::raw(&y), " hx-get=\"/a\" id=\"x\" class=\"y\"");
+}
+
+#[test]
+fn tuple_and_unit_structs() {
+    #[derive(Spread)]
+    struct Title<'a> {
+        title: &'a str,
+    }
+
+    #[derive(Spread)]
+    struct Newtype<'a>(Title<'a>);
+
+    #[derive(Spread)]
+    struct Pair<'a>(Title<'a>, Option<Title<'a>>);
+
+    #[derive(Spread)]
+    #[spread(attrs(r#type = "button", aria-pressed = false, "data-x" = 1, disabled))]
+    struct Toggle;
+
+    assert_eq!(Spread::raw(&Newtype(Title { title: "a" })), " title=\"a\"");
+    assert_eq!(
+        Spread::raw(&Pair(Title { title: "a" }, Some(Title { title: "b" }))),
+        " title=\"a\" title=\"b\""
+    );
+    assert_eq!(
+        Spread::raw(&Toggle),
+        " type=\"button\" aria-pressed=\"false\" data-x=\"1\" disabled"
+    );
+}
+
+#[test]
+fn field_options() {
+    fn is_zero(n: &u32) -> bool {
+        *n == 0
+    }
+
+    fn px(n: &u32) -> String {
+        format!("{n}px")
+    }
+
+    #[derive(Spread)]
+    struct A<'a> {
+        #[spread(skip)]
+        _internal: Vec<u8>,
+        #[spread(bool)]
+        disabled: bool,
+        #[spread(bool)]
+        hidden: bool,
+        #[spread(skip_if = "is_zero", with = "px")]
+        width: u32,
+        #[spread(skip_if = "is_zero")]
+        height: u32,
+        #[spread(default = "lazy")]
+        loading: Option<&'a str>,
+        #[spread(with = "px")]
+        #[escape_value]
+        size: Option<u32>,
+    }
+
+    let y = A {
+        _internal: vec![],
+        disabled: true,
+        hidden: false,
+        width: 10,
+        height: 0,
+        loading: None,
+        size: Some(2),
+    };
+
+    assert_eq!(
+        Spread::raw(&y),
+        " disabled width=\"10px\" loading=\"lazy\" size=\"2px\""
+    );
+
+    let y = A {
+        _internal: vec![],
+        disabled: false,
+        hidden: true,
+        width: 0,
+        height: 5,
+        loading: Some("eager"),
+        size: None,
+    };
+
+    assert_eq!(Spread::raw(&y), " hidden height=\"5\" loading=\"eager\"");
+}
+
+#[test]
+fn rename_all() {
+    #[derive(Spread)]
+    #[spread(rename_all = "kebab-case")]
+    struct A<'a> {
+        aria_label: &'a str,
+        r#type: &'a str,
+        #[rename = "data_id"]
+        data_id: u32,
+    }
+
+    let y = A {
+        aria_label: "Close",
+        r#type: "button",
+        data_id: 1,
+    };
+
+    assert_eq!(
+        Spread::raw(&y),
+        " aria-label=\"Close\" type=\"button\" data_id=\"1\""
+    );
+
+    #[derive(Spread)]
+    #[prefix = "hx"]
+    #[spread(rename_all = "camelCase")]
+    struct B {
+        push_url_now: bool,
+    }
+
+    assert_eq!(
+        Spread::raw(&B { push_url_now: true }),
+        " hx-pushUrlNow=\"true\""
+    );
+
+    #[derive(Spread)]
+    #[spread(rename_all = "SCREAMING-KEBAB-CASE")]
+    struct C {
+        x_y: u8,
+    }
+
+    assert_eq!(Spread::raw(&C { x_y: 1 }), " X-Y=\"1\"");
+}
+
+#[test]
+fn raw_identifier_names() {
+    #[derive(Spread)]
+    struct A<'a> {
+        r#type: &'a str,
+        r#for: &'a str,
+    }
+
+    let y = A {
+        r#type: "text",
+        r#for: "name",
+    };
+
+    assert_eq!(Spread::raw(&y), " type=\"text\" for=\"name\"");
+}
+
+#[test]
+fn manual_impl() {
+    struct Hidden(bool);
+
+    impl Spread for Hidden {
+        fn spread_to(&self, attrs: &mut dyn lfml::AttributeSink) {
+            if self.0 {
+                attrs.attribute("hidden", None);
+                attrs.attribute("data-x", Some(format_args!("{}", 1)));
+            }
+        }
+    }
+
+    #[derive(Spread)]
+    struct A {
+        #[spread(flatten)]
+        hidden: Hidden,
+        id: u8,
+    }
+
+    assert_eq!(Spread::raw(&Hidden(true)), " hidden data-x=\"1\"");
+
+    let mut buf = String::from("<p");
+    Spread::spread_to(
+        &A {
+            hidden: Hidden(true),
+            id: 1,
+        },
+        &mut buf,
+    );
+    assert_eq!(buf, "<p hidden data-x=\"1\" id=\"1\"");
+
+    let a = A {
+        hidden: Hidden(true),
+        id: 2,
+    };
+    assert_eq!(
+        lfml::html! { a data-x="0" @(a) {} }.as_inner(),
+        "<a data-x=\"1\" hidden id=\"2\"></a>"
+    );
+}
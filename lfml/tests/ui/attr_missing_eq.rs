@@ -0,0 +1,5 @@
+fn main() {
+    lfml::html! {
+        a href "/" { "home" }
+    };
+}
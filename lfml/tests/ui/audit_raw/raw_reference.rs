@@ -0,0 +1,8 @@
+#![deny(deprecated)]
+
+use lfml::{html, PreEscaped};
+
+fn main() {
+    let icon = PreEscaped(String::from("<svg></svg>"));
+    html! { button { (&icon) "Save" } };
+}
@@ -0,0 +1,8 @@
+#![deny(deprecated)]
+
+use lfml::html;
+
+fn main() {
+    let icon = lfml::raw("<svg></svg>");
+    html! { button { (icon) "Save" } };
+}
@@ -0,0 +1,6 @@
+fn main() {
+    let x = Some(1);
+    lfml::html! {
+        p { [x] }
+    };
+}
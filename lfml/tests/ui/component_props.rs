@@ -0,0 +1,11 @@
+use lfml::{component, html, Markup};
+
+#[component]
+fn Card(title: &str, children: Markup) -> Markup {
+    html! { h2 { (title) } (children) }
+}
+
+fn main() {
+    html! { Card { "missing title" } };
+    html! { Card title="x" colour="red" { "unknown prop" } };
+}
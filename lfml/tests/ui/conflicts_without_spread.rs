@@ -0,0 +1,11 @@
+use lfml::html;
+
+fn main() {
+    html! {
+        a href="/" @conflicts(merge) {}
+    };
+
+    html! {
+        a href="/" @conflicts(first_wins) {}
+    };
+}
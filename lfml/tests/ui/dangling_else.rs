@@ -0,0 +1,8 @@
+fn main() {
+    lfml::html! {
+        p { "x" }
+        @else {
+            p { "y" }
+        }
+    };
+}
@@ -0,0 +1,8 @@
+use lfml::html;
+
+fn main() {
+    let href = "/b";
+    html! {
+        a href="/a" title="x" href=(href) {}
+    };
+}
@@ -0,0 +1,7 @@
+use lfml::html;
+
+fn main() {
+    html! {
+        a class="a" .b {}
+    };
+}
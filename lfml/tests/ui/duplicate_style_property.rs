@@ -0,0 +1,7 @@
+use lfml::html;
+
+fn main() {
+    html! {
+        p style { color: "red"; margin: "0"; color: "blue" } {}
+    };
+}
@@ -0,0 +1,16 @@
+use lfml::{component, html, Markup};
+
+struct Slot(&'static str);
+
+impl lfml::Render for Slot {
+    fn markup_to_string(&self, buf: &mut String) {
+        buf.push_str(self.0);
+    }
+}
+
+#[component]
+fn Button(icon: Slot) -> Markup {
+    html! { button { (icon) } }
+}
+
+fn main() {}
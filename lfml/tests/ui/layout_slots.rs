@@ -0,0 +1,11 @@
+use lfml::{component, html, Markup, Slot};
+
+#[component]
+fn Base(content: Slot) -> Markup {
+    html! { main { @slot content; } }
+}
+
+fn main() {
+    html! { Base { @slot sidebar { "unknown slot" } } };
+    html! { Base { @slot content { "x" } p { "not a slot" } } };
+}
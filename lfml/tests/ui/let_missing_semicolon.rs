@@ -0,0 +1,5 @@
+fn main() {
+    lfml::html! {
+        @let x = 1
+    };
+}
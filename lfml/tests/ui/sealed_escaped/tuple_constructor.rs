@@ -0,0 +1,6 @@
+use lfml::Escaped;
+
+fn main() {
+    let user_input = String::from("<script>");
+    let _ = Escaped(user_input);
+}
@@ -0,0 +1,18 @@
+use lfml::Spread;
+
+#[derive(Spread)]
+#[spread(bound = "T Display")]
+struct A<T> {
+    value: T,
+}
+
+struct NotDisplay;
+
+#[derive(Spread)]
+struct B<T> {
+    value: T,
+}
+
+fn main() {
+    let _ = lfml::Spread::raw(&B { value: NotDisplay });
+}
@@ -0,0 +1,21 @@
+use lfml::Spread;
+
+#[derive(Spread)]
+struct A {
+    #[spread(bool)]
+    checked: Option<bool>,
+}
+
+#[derive(Spread)]
+struct B {
+    #[spread(default = "x")]
+    title: String,
+}
+
+#[derive(Spread)]
+struct C {
+    #[spread(skip_when = "f")]
+    title: String,
+}
+
+fn main() {}
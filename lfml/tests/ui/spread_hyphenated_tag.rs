@@ -0,0 +1,11 @@
+#[derive(lfml::Spread)]
+struct Attrs {
+    foo: String,
+}
+
+fn main() {
+    let attrs = Attrs { foo: "x".into() };
+    lfml::html! {
+        my-element @(attrs);
+    };
+}
@@ -0,0 +1,15 @@
+use lfml::Spread;
+
+#[derive(Spread)]
+#[spread(rename_all = "kebab_case")]
+struct A {
+    aria_label: String,
+}
+
+#[derive(Spread)]
+#[spread(rename_all = "kebab-case", attrs(hidden))]
+enum B {
+    C { aria_label: String },
+}
+
+fn main() {}
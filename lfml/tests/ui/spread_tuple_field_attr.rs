@@ -0,0 +1,11 @@
+use lfml::Spread;
+
+#[derive(Spread)]
+struct Title<'a> {
+    title: &'a str,
+}
+
+#[derive(Spread)]
+struct Wrapper<'a>(#[rename = "x"] Title<'a>);
+
+fn main() {}
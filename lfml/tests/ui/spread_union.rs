@@ -0,0 +1,6 @@
+#[derive(lfml::Spread)]
+union Attrs {
+    foo: u32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use lfml::html;
+
+fn main() {
+    html! {
+        p style { color: ; margin: "0" } {}
+    };
+}
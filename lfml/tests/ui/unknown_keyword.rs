@@ -0,0 +1,8 @@
+fn main() {
+    let x = true;
+    lfml::html! {
+        @iff x {
+            p { "x" }
+        }
+    };
+}
@@ -0,0 +1,6 @@
+fn main() {
+    lfml::html! {
+        p { "x" }
+        br
+    };
+}